@@ -0,0 +1,24 @@
+#!/usr/bin/env sh
+
+# Script for regenerating the weights of the nftmart pallets on the reference hardware.
+
+set -e
+
+PROJECT_ROOT=`git rev-parse --show-toplevel`
+
+cd "$PROJECT_ROOT"
+cargo build --release -p node-cli --features runtime-benchmarks
+
+for PALLET in nftmart_config nftmart_nft; do
+  ./target/release/substrate benchmark \
+    --chain=dev \
+    --steps=50 \
+    --repeat=20 \
+    --pallet="$PALLET" \
+    --extrinsic='*' \
+    --execution=wasm \
+    --wasm-execution=compiled \
+    --heap-pages=4096 \
+    --template=./.maintain/frame-weight-template.hbs \
+    --output="./pallets/$(echo $PALLET | tr '_' '-')/src/weights.rs"
+done
@@ -116,10 +116,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 296,
+	spec_version: 297,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 2,
+	transaction_version: 3,
 };
 
 /// The BABE epoch configuration at genesis.
@@ -260,6 +260,9 @@ pub enum ProxyType {
 	NonTransfer,
 	Governance,
 	Staking,
+	ClassAdmin,
+	ClassMinter,
+	ClassMetadataEditor,
 }
 impl Default for ProxyType { fn default() -> Self { Self::Any } }
 impl InstanceFilter<Call> for ProxyType {
@@ -282,6 +285,35 @@ impl InstanceFilter<Call> for ProxyType {
 				Call::Treasury(..)
 			),
 			ProxyType::Staking => matches!(c, Call::Staking(..)),
+			ProxyType::ClassAdmin => matches!(
+				c,
+				Call::Nftmart(nftmart_nft::Call::mint(..)) |
+				Call::Nftmart(nftmart_nft::Call::update_class(..)) |
+				Call::Nftmart(nftmart_nft::Call::update_token_metadata(..)) |
+				Call::Nftmart(nftmart_nft::Call::freeze_metadata(..)) |
+				Call::Nftmart(nftmart_nft::Call::set_class_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::clear_class_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::set_token_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::clear_token_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::add_class_admin(..)) |
+				Call::Nftmart(nftmart_nft::Call::remove_class_admin(..)) |
+				Call::Nftmart(nftmart_nft::Call::set_drop(..)) |
+				Call::Nftmart(nftmart_nft::Call::remove_drop(..)) |
+				Call::Nftmart(nftmart_nft::Call::set_class_currencies(..)) |
+				Call::Nftmart(nftmart_nft::Call::revoke(..)) |
+				Call::Nftmart(nftmart_nft::Call::reassign(..))
+			),
+			ProxyType::ClassMinter => matches!(c, Call::Nftmart(nftmart_nft::Call::mint(..))),
+			ProxyType::ClassMetadataEditor => matches!(
+				c,
+				Call::Nftmart(nftmart_nft::Call::update_class(..)) |
+				Call::Nftmart(nftmart_nft::Call::update_token_metadata(..)) |
+				Call::Nftmart(nftmart_nft::Call::freeze_metadata(..)) |
+				Call::Nftmart(nftmart_nft::Call::set_class_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::clear_class_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::set_token_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::clear_token_attribute(..))
+			),
 		}
 	}
 	fn is_superset(&self, o: &Self) -> bool {
@@ -290,11 +322,24 @@ impl InstanceFilter<Call> for ProxyType {
 			(ProxyType::Any, _) => true,
 			(_, ProxyType::Any) => false,
 			(ProxyType::NonTransfer, _) => true,
+			(ProxyType::ClassAdmin, ProxyType::ClassMinter) => true,
+			(ProxyType::ClassAdmin, ProxyType::ClassMetadataEditor) => true,
 			_ => false,
 		}
 	}
 }
 
+pub struct ClassAdminProxy;
+impl traits::Convert<nftmart_nft::ClassAdminRole, ProxyType> for ClassAdminProxy {
+	fn convert(role: nftmart_nft::ClassAdminRole) -> ProxyType {
+		match role {
+			nftmart_nft::ClassAdminRole::Admin => ProxyType::ClassAdmin,
+			nftmart_nft::ClassAdminRole::Minter => ProxyType::ClassMinter,
+			nftmart_nft::ClassAdminRole::MetadataEditor => ProxyType::ClassMetadataEditor,
+		}
+	}
+}
+
 impl pallet_proxy::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
@@ -1102,10 +1147,13 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = ();
+	type FirstCreatedCurrencyId = FirstCreatedCurrencyId;
 }
 
 parameter_types! {
 	pub const GetNativeCurrencyId: sp_core::constants_types::CurrencyId = sp_core::constants_types::NATIVE_CURRENCY_ID;
+	// Currencies created for fractionalized tokens never collide with the ones accepted by governance.
+	pub const FirstCreatedCurrencyId: sp_core::constants_types::CurrencyId = 1 << 31;
 }
 
 pub type AdaptedBasicCurrency = BasicCurrencyAdapter<Runtime, Balances, sp_core::constants_types::Amount, sp_core::constants_types::Moment>;
@@ -1118,11 +1166,18 @@ impl orml_currencies::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const MaxNestingDepth: u32 = 5;
+}
+
 impl orml_nft::Config for Runtime {
 	type ClassId = sp_core::constants_types::ClassId;
 	type TokenId = sp_core::constants_types::TokenId;
+	type Quantity = sp_core::constants_types::Quantity;
 	type ClassData = nftmart_nft::ClassData<BlockNumber>;
 	type TokenData = nftmart_nft::TokenData<BlockNumber>;
+	type TokenAccount = nftmart_nft::TokenAccount<Self>;
+	type MaxNestingDepth = MaxNestingDepth;
 }
 
 parameter_types! {
@@ -1130,6 +1185,18 @@ parameter_types! {
 	pub const CreateTokenDeposit: Balance = 1 * CENTS;
 	pub const MetaDataByteDeposit: Balance = 10 * MILLICENTS;
 	pub const NftModuleId: ModuleId = ModuleId(*b"nftmart*");
+	pub PlatformFeeAccount: AccountId = Treasury::account_id();
+	pub const MaxRoyaltyRate: Perbill = Perbill::from_percent(50);
+	pub const MaxExpiredOrdersPerBlock: u32 = 50;
+	pub const MaxExpiredRentalsPerBlock: u32 = 50;
+	pub const MaxAuctionsEndingPerBlock: u32 = 50;
+	pub const MaxBundleSize: u32 = 20;
+	pub const MaxOrdersPerToken: u32 = 50;
+	pub const MaxAttributeKeyLength: u32 = 64;
+	pub const MaxAttributeValueLength: u32 = 256;
+	pub const MaxClassCurrencies: u32 = 8;
+	pub const MaxProofLength: u32 = 32;
+	pub const MaxDropMetadataLength: u32 = 1024;
 }
 
 impl nftmart_nft::Config for Runtime {
@@ -1141,10 +1208,30 @@ impl nftmart_nft::Config for Runtime {
 	type Currency = Balances;
 	type MultiCurrency = Currencies;
 	type CategoryId = sp_core::constants_types::CategoryId;
+	type PlatformFeeAccount = PlatformFeeAccount;
+	type MaxRoyaltyRate = MaxRoyaltyRate;
+	type MaxExpiredOrdersPerBlock = MaxExpiredOrdersPerBlock;
+	type MaxExpiredRentalsPerBlock = MaxExpiredRentalsPerBlock;
+	type MaxAuctionsEndingPerBlock = MaxAuctionsEndingPerBlock;
+	type MaxBundleSize = MaxBundleSize;
+	type MaxOrdersPerToken = MaxOrdersPerToken;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type MaxClassCurrencies = MaxClassCurrencies;
+	type MaxProofLength = MaxProofLength;
+	type MaxDropMetadataLength = MaxDropMetadataLength;
+	type ClassAdminProxy = ClassAdminProxy;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as traits::Verify>::Signer;
+	type CurrencyFactory = Currencies;
+	type WeightInfo = nftmart_nft::weights::SubstrateWeight<Runtime>;
 }
 
 impl nftmart_config::Config for Runtime {
 	type Event = Event;
+	type CurrencyId = sp_core::constants_types::CurrencyId;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type WeightInfo = nftmart_config::weights::SubstrateWeight<Runtime>;
 }
 
 construct_runtime!(
@@ -1447,8 +1534,8 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl nftmart_rpc_runtime_api::NFTMartApi<Block> for Runtime {
-		fn mint_token_deposit(metadata_len: u32, quantity: u32) -> (Balance, Balance) {
+	impl nftmart_rpc_runtime_api::NFTMartApi<Block, AccountId, BlockNumber> for Runtime {
+		fn mint_token_deposit(metadata_len: u32, quantity: nftmart_rpc_runtime_api::Quantity) -> (Balance, Balance) {
 			Nftmart::mint_token_deposit(metadata_len, quantity)
 		}
 		fn add_class_admin_deposit(admin_count: u32) -> Balance {
@@ -1457,6 +1544,30 @@ impl_runtime_apis! {
 		fn create_class_deposit(metadata_len: u32, name_len: u32, description_len: u32) -> (Balance, Balance) {
 			Nftmart::create_class_deposit(metadata_len, name_len, description_len)
 		}
+		fn trade_proceeds(class_id: sp_core::constants_types::ClassId, price: Balance) -> (Balance, Balance, Balance) {
+			Nftmart::trade_proceeds(class_id, price)
+		}
+		fn tokens_of(who: AccountId, page: u32, page_size: u32) -> Vec<(nftmart_rpc_runtime_api::ClassId, nftmart_rpc_runtime_api::TokenId, nftmart_rpc_runtime_api::Quantity)> {
+			Nftmart::tokens_of(&who, page, page_size)
+		}
+		fn class_info(class_id: nftmart_rpc_runtime_api::ClassId) -> Option<orml_nft::ClassInfoOf<Runtime>> {
+			Nftmart::class_info(class_id)
+		}
+		fn token_info(class_id: nftmart_rpc_runtime_api::ClassId, token_id: nftmart_rpc_runtime_api::TokenId) -> Option<orml_nft::TokenInfoOf<Runtime>> {
+			Nftmart::token_info(class_id, token_id)
+		}
+		fn orders_of_token(class_id: nftmart_rpc_runtime_api::ClassId, token_id: nftmart_rpc_runtime_api::TokenId, page: u32, page_size: u32) -> Vec<nftmart_nft::OrderInfoOf<Runtime>> {
+			Nftmart::orders_of_token(class_id, token_id, page, page_size)
+		}
+		fn orders_by_account(who: AccountId, page: u32, page_size: u32) -> Vec<nftmart_nft::OrderInfoOf<Runtime>> {
+			Nftmart::orders_by_account(&who, page, page_size)
+		}
+		fn orders_of_category(category_id: nftmart_rpc_runtime_api::CategoryId, page: u32, page_size: u32) -> Vec<nftmart_nft::OrderInfoOf<Runtime>> {
+			Nftmart::orders_of_category(category_id, page, page_size)
+		}
+		fn categories(page: u32, page_size: u32) -> Vec<(nftmart_rpc_runtime_api::CategoryId, nftmart_nft::CategoryData)> {
+			Nftmart::all_categories(page, page_size)
+		}
 	}
 
 	impl pallet_mmr::primitives::MmrApi<
@@ -1573,6 +1684,8 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_treasury, Treasury);
 			add_benchmark!(params, batches, pallet_utility, Utility);
 			add_benchmark!(params, batches, pallet_vesting, Vesting);
+			add_benchmark!(params, batches, nftmart_config, Config);
+			add_benchmark!(params, batches, nftmart_nft, Nftmart);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
@@ -618,6 +618,22 @@ impl<T: Config> Module<T> {
 		})
 	}
 
+	/// Whether the delegatee is a proxy of the delegator allowed to make every call that
+	/// `proxy_type` allows.
+	///
+	/// Parameters:
+	/// - `delegator`: The delegator account.
+	/// - `delegatee`: The account that may be a proxy of the `delegator`.
+	/// - `proxy_type`: The permissions the proxy account needs at least.
+	pub fn has_proxy_delegate(
+		delegator: &T::AccountId,
+		delegatee: &T::AccountId,
+		proxy_type: &T::ProxyType,
+	) -> bool {
+		Proxies::<T>::get(delegator).0.iter()
+			.any(|x| &x.delegate == delegatee && x.proxy_type.is_superset(proxy_type))
+	}
+
 	pub fn deposit(num_proxies: u32) -> BalanceOf<T> {
 		if num_proxies == 0 {
 			Zero::zero()
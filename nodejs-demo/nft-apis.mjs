@@ -169,7 +169,7 @@ async function demo_create_order(ws, keyring, classID, tokenID, account) {
 		NativeCurrencyID,
 		price,
 		categoryId,
-		classID, tokenID,
+		classID, tokenID, 1,
 		deposit,
 		currentBlockNumber.add(bnToBn('1000')),
 	);
@@ -277,7 +277,7 @@ async function demo_burn_nft(ws, keyring, classID, tokenID, account) {
 	let moduleMetadata = await getModules(api);
 	account = keyring.addFromUri(account);
 
-	const call = api.tx.nftmart.burn(classID, tokenID);
+	const call = api.tx.nftmart.burn(classID, tokenID, 1);
 	const feeInfo = await call.paymentInfo(account);
 	console.log("The fee of the call: %s.", feeInfo.partialFee / unit);
 	let [a, b] = waitTx(moduleMetadata);
@@ -296,7 +296,7 @@ async function demo_transfer_nft(ws, keyring, classID, tokenID, from, to) {
 	from = keyring.addFromUri(from);
 	to = keyring.addFromUri(to).address;
 
-	const call = api.tx.nftmart.transfer(to, classID, tokenID);
+	const call = api.tx.nftmart.transfer(to, classID, tokenID, 1);
 	const feeInfo = await call.paymentInfo(from);
 	console.log("The fee of the call: %s.", feeInfo.partialFee / unit);
 
@@ -495,8 +495,12 @@ async function demo_create_class(ws, keyring, account) {
 	// 	Transferable = 0b00000001,
 	// 	/// Token can be burned
 	// 	Burnable = 0b00000010,
+	// 	/// Metadata of the class and its tokens can not be updated any more
+	// 	MetadataFrozen = 0b00000100,
+	// 	/// Tokens can be revoked or reassigned by the class owner, only for non-transferable classes
+	// 	Revocable = 0b00001000,
 	// }
-	await api.tx.nftmart.createClass("https://xx.com/aa.jpg", "aaa", "bbbb", 1 | 2).signAndSend(account, a);
+	await api.tx.nftmart.createClass("https://xx.com/aa.jpg", "aaa", "bbbb", 1 | 2, 0, null, null).signAndSend(account, a);
 	await b();
 	process.exit();
 }
@@ -75,6 +75,12 @@ export async function getApi(dest) {
 
 	const types = {
 		Properties: 'u8',
+		ClassAdminRole: {
+			_enum: ['Admin', 'Minter', 'MetadataEditor'],
+		},
+		ProxyType: {
+			_enum: ['Any', 'NonTransfer', 'Governance', 'Staking', 'ClassAdmin', 'ClassMinter', 'ClassMetadataEditor'],
+		},
 		NFTMetadata: 'Vec<u8>',
 		BlockNumber: 'u32',
 		BlockNumberOf: 'BlockNumber',
@@ -84,7 +90,9 @@ export async function getApi(dest) {
 			price: 'Compact<Balance>',
 			deposit: 'Compact<Balance>',
 			deadline: 'Compact<BlockNumberOf>',
-			categoryId: 'Compact<CategoryIdOf>'
+			categoryId: 'Compact<CategoryIdOf>',
+			quantity: 'Compact<QuantityOf>',
+			byTokenOwner: 'bool'
 		},
 
 		CategoryId: 'u32',
@@ -103,7 +111,7 @@ export async function getApi(dest) {
 		ClassIdOf: 'ClassId',
 		ClassInfoOf: {
 			metadata: 'NFTMetadata',
-			totalIssuance: 'TokenId',
+			totalIssuance: 'Quantity',
 			owner: 'AccountId',
 			data: 'ClassData'
 		},
@@ -112,15 +120,111 @@ export async function getApi(dest) {
 			properties: 'Properties',
 			name: 'Vec<u8>',
 			description: 'Vec<u8>',
-			createBlock: 'Compact<BlockNumberOf>'
+			createBlock: 'Compact<BlockNumberOf>',
+			royaltyRate: 'Perbill',
+			maxSupply: 'Option<Quantity>',
+			maxMintPerAccount: 'Option<Quantity>'
 		},
 
 		TokenId: 'u64',
 		TokenIdOf: 'TokenId',
-		TokenInfoOf: {metadata: 'NFTMetadata', owner: 'AccountId', data: 'TokenData'},
+		Quantity: 'u32',
+		QuantityOf: 'Quantity',
+		TokenInfoOf: {metadata: 'NFTMetadata', quantity: 'QuantityOf', data: 'TokenData'},
 		TokenData: {
 			deposit: 'Compact<Balance>',
 			createBlock: 'Compact<BlockNumberOf>'
+		},
+
+		GlobalId: 'u64',
+		AuctionInfo: {
+			bid: 'Option<(AccountId, Balance)>',
+			start: 'BlockNumber',
+			end: 'Option<BlockNumber>'
+		},
+		AuctionItem: {
+			owner: 'AccountId',
+			currencyId: 'Compact<CurrencyIdOf>',
+			classId: 'Compact<ClassIdOf>',
+			tokenId: 'Compact<TokenIdOf>',
+			reservePrice: 'Compact<Balance>',
+			minRaise: 'Compact<Balance>',
+			deposit: 'Compact<Balance>'
+		},
+		DutchOrderData: {
+			owner: 'AccountId',
+			currencyId: 'Compact<CurrencyIdOf>',
+			startPrice: 'Compact<Balance>',
+			floorPrice: 'Compact<Balance>',
+			startBlock: 'Compact<BlockNumberOf>',
+			endBlock: 'Compact<BlockNumberOf>',
+			step: 'Compact<BlockNumberOf>',
+			deposit: 'Compact<Balance>',
+			categoryId: 'Compact<CategoryIdOf>'
+		},
+		OfferData: {
+			owner: 'AccountId',
+			currencyId: 'Compact<CurrencyIdOf>',
+			price: 'Compact<Balance>',
+			quantity: 'Compact<QuantityOf>',
+			deposit: 'Compact<Balance>',
+			deadline: 'Compact<BlockNumberOf>'
+		},
+		BundleOrderData: {
+			owner: 'AccountId',
+			currencyId: 'Compact<CurrencyIdOf>',
+			price: 'Compact<Balance>',
+			deposit: 'Compact<Balance>',
+			deadline: 'Compact<BlockNumberOf>',
+			categoryId: 'Compact<CategoryIdOf>',
+			tokens: 'Vec<(ClassIdOf, TokenIdOf)>'
+		},
+		DropData: {
+			currencyId: 'Compact<CurrencyIdOf>',
+			price: 'Compact<Balance>',
+			start: 'Compact<BlockNumberOf>',
+			end: 'Compact<BlockNumberOf>',
+			maxPerWallet: 'Option<QuantityOf>',
+			allowlist: 'Option<H256>',
+			metadata: 'NFTMetadata'
+		},
+		MintVoucher: {
+			signer: 'AccountId',
+			classId: 'Compact<ClassIdOf>',
+			metadataHash: 'H256',
+			quantity: 'Compact<QuantityOf>',
+			currencyId: 'Compact<CurrencyIdOf>',
+			price: 'Compact<Balance>',
+			nonce: 'Compact<u64>',
+			expiry: 'Compact<BlockNumberOf>'
+		},
+		OffchainSignature: 'MultiSignature',
+		RentalListing: {
+			owner: 'AccountId',
+			currencyId: 'Compact<CurrencyIdOf>',
+			pricePerBlock: 'Compact<Balance>',
+			maxDuration: 'Compact<BlockNumberOf>'
+		},
+		RentalData: {
+			lender: 'AccountId',
+			user: 'AccountId',
+			end: 'Compact<BlockNumberOf>'
+		},
+		VaultData: {
+			owner: 'AccountId',
+			quantity: 'Compact<QuantityOf>',
+			shareCurrencyId: 'Compact<CurrencyIdOf>',
+			shares: 'Compact<Balance>',
+			currencyId: 'Compact<CurrencyIdOf>',
+			reservePrice: 'Compact<Balance>',
+			proceeds: 'Option<Balance>'
+		},
+		AttributeValue: {
+			_enum: {
+				Bool: 'bool',
+				Integer: 'u64',
+				Bytes: 'Vec<u8>'
+			}
 		}
 	};
 
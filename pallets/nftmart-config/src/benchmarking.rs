@@ -0,0 +1,48 @@
+// Benchmarks for nftmart-config Pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account, impl_benchmark_test_suite};
+use crate::Pallet as NftmartConfig;
+
+const SEED: u32 = 0;
+
+benchmarks! {
+	add_whitelist {
+		let who: T::AccountId = account("who", 0, SEED);
+	}: _(RawOrigin::Root, who.clone())
+	verify {
+		assert!(NftmartConfig::<T>::account_whitelist(&who).is_some());
+	}
+
+	remove_whitelist {
+		let who: T::AccountId = account("who", 0, SEED);
+		NftmartConfig::<T>::add_whitelist(RawOrigin::Root.into(), who.clone())?;
+	}: _(RawOrigin::Root, who.clone())
+	verify {
+		assert!(NftmartConfig::<T>::account_whitelist(&who).is_none());
+	}
+
+	accept_currency {
+		let currency_id = T::NativeCurrencyId::get();
+	}: _(RawOrigin::Root, currency_id, 100)
+	verify {
+		assert_eq!(NftmartConfig::<T>::accepted_currencies(currency_id), Some(100));
+	}
+
+	remove_currency {
+		let currency_id = T::NativeCurrencyId::get();
+		NftmartConfig::<T>::accept_currency(RawOrigin::Root.into(), currency_id, 100)?;
+	}: _(RawOrigin::Root, currency_id)
+	verify {
+		assert!(NftmartConfig::<T>::accepted_currencies(currency_id).is_none());
+	}
+}
+
+impl_benchmark_test_suite!(
+	NftmartConfig,
+	crate::mock::ExtBuilder::default().build(),
+	crate::mock::Runtime,
+);
@@ -5,11 +5,15 @@ use frame_support::{
 	transactional
 };
 use frame_system::pallet_prelude::*;
+use sp_core::constants_types::Balance;
 
 mod mock;
 mod tests;
+mod benchmarking;
+pub mod weights;
 
 pub use module::*;
+pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod module {
@@ -18,6 +22,16 @@ pub mod module {
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency ID type used for trading
+		type CurrencyId: Parameter + Member + Copy + MaybeSerializeDeserialize;
+
+		/// The native currency, accepted for trading without a price floor unless one is set.
+		#[pallet::constant]
+		type NativeCurrencyId: Get<Self::CurrencyId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
@@ -31,6 +45,10 @@ pub mod module {
 		AddWhitelist(T::AccountId),
 		/// RemoveWhitelist \[who\]
 		RemoveWhitelist(T::AccountId),
+		/// AcceptedCurrency \[currency_id, min_price\]
+		AcceptedCurrency(T::CurrencyId, Balance),
+		/// RemovedCurrency \[currency_id\]
+		RemovedCurrency(T::CurrencyId),
 	}
 
 	#[pallet::pallet]
@@ -67,10 +85,15 @@ pub mod module {
 	#[pallet::getter(fn account_whitelist)]
 	pub type AccountWhitelist<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
 
+	/// Currencies accepted for trading and the minimum price of an order in each of them
+	#[pallet::storage]
+	#[pallet::getter(fn accepted_currencies)]
+	pub type AcceptedCurrencies<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, Balance>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// add an account into whitelist
-		#[pallet::weight((100_000, DispatchClass::Operational))]
+		#[pallet::weight((T::WeightInfo::add_whitelist(), DispatchClass::Operational))]
 		#[transactional]
 		pub fn add_whitelist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
@@ -80,7 +103,7 @@ pub mod module {
 		}
 
 		/// remove an account from whitelist
-		#[pallet::weight((100_000, DispatchClass::Operational))]
+		#[pallet::weight((T::WeightInfo::remove_whitelist(), DispatchClass::Operational))]
 		#[transactional]
 		pub fn remove_whitelist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
@@ -88,5 +111,42 @@ pub mod module {
 			Self::deposit_event(Event::RemoveWhitelist(who));
 			Ok((None, Pays::No).into())
 		}
+
+		/// accept a currency for trading, orders priced below `min_price` are rejected
+		#[pallet::weight((T::WeightInfo::accept_currency(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn accept_currency(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			#[pallet::compact] min_price: Balance,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			AcceptedCurrencies::<T>::insert(currency_id, min_price);
+			Self::deposit_event(Event::AcceptedCurrency(currency_id, min_price));
+			Ok((None, Pays::No).into())
+		}
+
+		/// stop accepting a currency for new orders, the native currency is still accepted without a price floor
+		#[pallet::weight((T::WeightInfo::remove_currency(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn remove_currency(origin: OriginFor<T>, currency_id: T::CurrencyId) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			AcceptedCurrencies::<T>::remove(currency_id);
+			Self::deposit_event(Event::RemovedCurrency(currency_id));
+			Ok((None, Pays::No).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The minimum price of an order in `currency_id`, `None` if the currency is not accepted.
+	pub fn min_order_price(currency_id: T::CurrencyId) -> Option<Balance> {
+		Self::accepted_currencies(currency_id).or_else(|| {
+			if currency_id == T::NativeCurrencyId::get() {
+				Some(0)
+			} else {
+				None
+			}
+		})
 	}
 }
@@ -118,8 +118,15 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 }
 
+parameter_types! {
+	pub const NativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
+}
+
 impl nftmart_config::Config for Runtime {
 	type Event = Event;
+	type CurrencyId = CurrencyId;
+	type NativeCurrencyId = NativeCurrencyId;
+	type WeightInfo = ();
 }
 
 use frame_system::Call as SystemCall;
@@ -3,6 +3,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
+use sp_core::constants_types::NATIVE_CURRENCY_ID;
 
 #[test]
 fn test_whitelist() {
@@ -23,3 +24,27 @@ fn test_whitelist() {
 		assert_eq!(None, NftmartConfig::account_whitelist(BOB));
 	});
 }
+
+#[test]
+fn test_accepted_currencies() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Some(0), NftmartConfig::min_order_price(NATIVE_CURRENCY_ID));
+		assert_eq!(None, NftmartConfig::min_order_price(1));
+		assert_noop!(
+			NftmartConfig::accept_currency(Origin::signed(ALICE), 1, 10),
+			DispatchError::BadOrigin,
+		);
+
+		assert_ok!(NftmartConfig::accept_currency(Origin::root(), 1, 10));
+		assert_eq!(last_event(), Event::nftmart_config(crate::Event::AcceptedCurrency(1, 10)));
+		assert_eq!(Some(10), NftmartConfig::min_order_price(1));
+		assert_ok!(NftmartConfig::accept_currency(Origin::root(), NATIVE_CURRENCY_ID, 5));
+		assert_eq!(Some(5), NftmartConfig::min_order_price(NATIVE_CURRENCY_ID));
+
+		assert_ok!(NftmartConfig::remove_currency(Origin::root(), 1));
+		assert_eq!(last_event(), Event::nftmart_config(crate::Event::RemovedCurrency(1)));
+		assert_eq!(None, NftmartConfig::min_order_price(1));
+		assert_ok!(NftmartConfig::remove_currency(Origin::root(), NATIVE_CURRENCY_ID));
+		assert_eq!(Some(0), NftmartConfig::min_order_price(NATIVE_CURRENCY_ID));
+	});
+}
@@ -1,17 +1,39 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-pub use sp_core::constants_types::Balance;
+use codec::Codec;
+pub use sp_core::constants_types::{Balance, ClassId, TokenId, Quantity, CurrencyId, CategoryId};
 pub use sp_std::vec::Vec;
-pub use nftmart_nft::NFTMetadata;
+pub use nftmart_nft::{NFTMetadata, ClassData, TokenData, CategoryData, OrderInfo};
+pub use orml_nft::{ClassInfo, TokenInfo};
 
 sp_api::decl_runtime_apis! {
-	/// The helper API to calculate deposit.
-	pub trait NFTMartApi {
+	/// The helper API to calculate deposit and query tokens, classes and orders.
+	#[api_version(2)]
+	pub trait NFTMartApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
 		/// mint_token_deposit
-		fn mint_token_deposit(metadata_len: u32, quantity: u32) -> (Balance, Balance);
+		fn mint_token_deposit(metadata_len: u32, quantity: Quantity) -> (Balance, Balance);
 		/// add_class_admin_deposit
 		fn add_class_admin_deposit(admin_count: u32) -> Balance;
 		/// create_class_deposit
 		fn create_class_deposit(metadata_len: u32, name_len: u32, description_len: u32) -> (Balance, Balance);
+		/// Split `price` of a token into `(platform_fee, royalty, seller_proceeds)`.
+		fn trade_proceeds(class_id: ClassId, price: Balance) -> (Balance, Balance, Balance);
+		/// `(class_id, token_id, quantity)` of the tokens held by `who`, `page_size` entries from `page`.
+		fn tokens_of(who: AccountId, page: u32, page_size: u32) -> Vec<(ClassId, TokenId, Quantity)>;
+		/// class_info
+		fn class_info(class_id: ClassId) -> Option<ClassInfo<Quantity, AccountId, ClassData<BlockNumber>>>;
+		/// token_info
+		fn token_info(class_id: ClassId, token_id: TokenId) -> Option<TokenInfo<Quantity, TokenData<BlockNumber>>>;
+		/// The orders on a token, `page_size` entries from `page`.
+		fn orders_of_token(class_id: ClassId, token_id: TokenId, page: u32, page_size: u32) -> Vec<OrderInfo<AccountId, ClassId, TokenId, Quantity, CurrencyId, CategoryId, BlockNumber>>;
+		/// The orders placed by `who`, `page_size` entries from `page`.
+		fn orders_by_account(who: AccountId, page: u32, page_size: u32) -> Vec<OrderInfo<AccountId, ClassId, TokenId, Quantity, CurrencyId, CategoryId, BlockNumber>>;
+		/// The orders in a category, `page_size` entries from `page`.
+		fn orders_of_category(category_id: CategoryId, page: u32, page_size: u32) -> Vec<OrderInfo<AccountId, ClassId, TokenId, Quantity, CurrencyId, CategoryId, BlockNumber>>;
+		/// The categories, `page_size` entries from `page`.
+		fn categories(page: u32, page_size: u32) -> Vec<(CategoryId, CategoryData)>;
 	}
 }
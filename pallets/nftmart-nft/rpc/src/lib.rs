@@ -1,21 +1,135 @@
 use std::sync::Arc;
+use codec::Codec;
+use serde::{Deserialize, Serialize};
 use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use sp_runtime::{generic::BlockId, traits::{Block as BlockT}};
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, Header as HeaderT}, PerThing};
 use sp_api::ProvideRuntimeApi;
 pub use nftmart_rpc_runtime_api::NFTMartApi as NFTMartRuntimeApi;
+use nftmart_rpc_runtime_api::{Balance, ClassId, TokenId, Quantity, CurrencyId, CategoryId, OrderInfo};
+
+/// The number of tokens returned by one page of `nftmart_tokensOf`.
+const TOKENS_PAGE_SIZE: u32 = 100;
+
+/// The number of orders returned by one page of `nftmart_ordersOfToken`, `nftmart_ordersByAccount`
+/// and `nftmart_ordersOfCategory`.
+const ORDERS_PAGE_SIZE: u32 = 100;
+
+/// The number of categories returned by one page of `nftmart_categories`.
+const CATEGORIES_PAGE_SIZE: u32 = 100;
+
+/// A token held by an account.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBalance {
+	pub class_id: ClassId,
+	pub token_id: TokenId,
+	pub quantity: Quantity,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassInfo<AccountId, BlockNumber> {
+	pub metadata: Bytes,
+	pub name: Bytes,
+	pub description: Bytes,
+	pub total_issuance: Quantity,
+	pub owner: AccountId,
+	pub deposit: String,
+	/// Bit flags of `nftmart_nft::ClassProperty`.
+	pub properties: u8,
+	pub create_block: BlockNumber,
+	/// Parts per billion of every trade price.
+	pub royalty_rate: u32,
+	pub max_supply: Option<Quantity>,
+	pub max_mint_per_account: Option<Quantity>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo<BlockNumber> {
+	pub metadata: Bytes,
+	pub quantity: Quantity,
+	pub deposit: String,
+	pub create_block: BlockNumber,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order<AccountId, BlockNumber> {
+	pub class_id: ClassId,
+	pub token_id: TokenId,
+	pub owner: AccountId,
+	pub currency_id: CurrencyId,
+	pub price: String,
+	pub deposit: String,
+	pub deadline: BlockNumber,
+	pub category_id: CategoryId,
+	pub quantity: Quantity,
+	pub by_token_owner: bool,
+}
+
+impl<AccountId, BlockNumber> From<OrderInfo<AccountId, ClassId, TokenId, Quantity, CurrencyId, CategoryId, BlockNumber>> for Order<AccountId, BlockNumber> {
+	fn from(order: OrderInfo<AccountId, ClassId, TokenId, Quantity, CurrencyId, CategoryId, BlockNumber>) -> Self {
+		Self {
+			class_id: order.class_id,
+			token_id: order.token_id,
+			owner: order.owner,
+			currency_id: order.currency_id,
+			price: format!("{}", order.price),
+			deposit: format!("{}", order.deposit),
+			deadline: order.deadline,
+			category_id: order.category_id,
+			quantity: order.quantity,
+			by_token_owner: order.by_token_owner,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Category {
+	pub category_id: CategoryId,
+	pub metadata: Bytes,
+	pub nft_count: String,
+}
 
 #[rpc]
-pub trait NFTMartApi {
+pub trait NFTMartApi<AccountId, BlockNumber> {
 	#[rpc(name = "nftmart_mintTokenDeposit")]
-	fn mint_token_deposit(&self, metadata_len: u32, quantity: u32) -> Result<(String, String)>;
+	fn mint_token_deposit(&self, metadata_len: u32, quantity: Quantity) -> Result<(String, String)>;
 
 	#[rpc(name = "nftmart_createClassDeposit")]
 	fn create_class_deposit(&self, metadata_len: u32, name_len: u32, description_len: u32) -> Result<(String, String)>;
 
 	#[rpc(name = "nftmart_addClassAdminDeposit")]
 	fn add_class_admin_deposit(&self, admin_count: u32) -> Result<String>;
+
+	#[rpc(name = "nftmart_tradeProceeds")]
+	fn trade_proceeds(&self, class_id: ClassId, price: String) -> Result<(String, String, String)>;
+
+	#[rpc(name = "nftmart_tokensOf")]
+	fn tokens_of(&self, who: AccountId, page: u32) -> Result<Vec<TokenBalance>>;
+
+	#[rpc(name = "nftmart_classInfo")]
+	fn class_info(&self, class_id: ClassId) -> Result<Option<ClassInfo<AccountId, BlockNumber>>>;
+
+	#[rpc(name = "nftmart_tokenInfo")]
+	fn token_info(&self, class_id: ClassId, token_id: TokenId) -> Result<Option<TokenInfo<BlockNumber>>>;
+
+	#[rpc(name = "nftmart_ordersOfToken")]
+	fn orders_of_token(&self, class_id: ClassId, token_id: TokenId, page: u32) -> Result<Vec<Order<AccountId, BlockNumber>>>;
+
+	#[rpc(name = "nftmart_ordersByAccount")]
+	fn orders_by_account(&self, who: AccountId, page: u32) -> Result<Vec<Order<AccountId, BlockNumber>>>;
+
+	#[rpc(name = "nftmart_ordersOfCategory")]
+	fn orders_of_category(&self, category_id: CategoryId, page: u32) -> Result<Vec<Order<AccountId, BlockNumber>>>;
+
+	#[rpc(name = "nftmart_categories")]
+	fn categories(&self, page: u32) -> Result<Vec<Category>>;
 }
 
 /// A struct that implements the [`NFTMartApi`].
@@ -48,11 +162,20 @@ impl From<Error> for i64 {
 	}
 }
 
-impl<C, Block> NFTMartApi for NFTMart<C, Block>
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query dispatch info.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId> NFTMartApi<AccountId, <<Block as BlockT>::Header as HeaderT>::Number> for NFTMart<C, Block>
 where
 	Block: BlockT,
 	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	C::Api: NFTMartRuntimeApi<Block>,
+	C::Api: NFTMartRuntimeApi<Block, AccountId, <<Block as BlockT>::Header as HeaderT>::Number>,
+	AccountId: Codec,
 {
 	/*
 		$ curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d   '{
@@ -66,7 +189,7 @@ where
 		{"id":1,"jsonrpc":"2.0","method":"nftmart_mintTokenDeposit","params":[4, 3]}
 		{"jsonrpc":"2.0","result":["1040000000000","3120000000000"],"id":1}
 	 */
-	fn mint_token_deposit(&self, metadata_len: u32, quantity: u32) -> Result<(String, String)> {
+	fn mint_token_deposit(&self, metadata_len: u32, quantity: Quantity) -> Result<(String, String)> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(self.client.info().best_hash);
 		api.mint_token_deposit(&at, metadata_len, quantity).map_err(|e| RpcError {
@@ -101,4 +224,105 @@ where
 			format!("{}", deposit)
 		})
 	}
+
+	/*
+		$ curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d   '{
+		 "jsonrpc":"2.0",
+		  "id":1,
+		  "method":"nftmart_tradeProceeds",
+		  "params": [0, "1000000000000"]
+		}'
+		{"jsonrpc":"2.0","result":["20000000000","100000000000","880000000000"],"id":1}
+	 */
+	fn trade_proceeds(&self, class_id: ClassId, price: String) -> Result<(String, String, String)> {
+		let price: Balance = price.parse().map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::DecodeError.into()),
+			message: "Unable to decode price.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.trade_proceeds(&at, class_id, price).map_err(runtime_error).map(|(platform_fee, royalty, proceeds)|{
+			(format!("{}", platform_fee), format!("{}", royalty), format!("{}", proceeds))
+		})
+	}
+
+	/*
+		$ curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d   '{
+		 "jsonrpc":"2.0",
+		  "id":1,
+		  "method":"nftmart_tokensOf",
+		  "params": ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", 0]
+		}'
+		{"jsonrpc":"2.0","result":[{"classId":0,"tokenId":0,"quantity":10}],"id":1}
+	 */
+	fn tokens_of(&self, who: AccountId, page: u32) -> Result<Vec<TokenBalance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.tokens_of(&at, who, page, TOKENS_PAGE_SIZE).map_err(runtime_error).map(|tokens| {
+			tokens.into_iter().map(|(class_id, token_id, quantity)| TokenBalance { class_id, token_id, quantity }).collect()
+		})
+	}
+
+	fn class_info(&self, class_id: ClassId) -> Result<Option<ClassInfo<AccountId, <<Block as BlockT>::Header as HeaderT>::Number>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.class_info(&at, class_id).map_err(runtime_error).map(|class| class.map(|class| ClassInfo {
+			metadata: class.metadata.into(),
+			name: class.data.name.into(),
+			description: class.data.description.into(),
+			total_issuance: class.total_issuance,
+			owner: class.owner,
+			deposit: format!("{}", class.data.deposit),
+			properties: class.data.properties.0.bits(),
+			create_block: class.data.create_block,
+			royalty_rate: class.data.royalty_rate.deconstruct(),
+			max_supply: class.data.max_supply,
+			max_mint_per_account: class.data.max_mint_per_account,
+		}))
+	}
+
+	fn token_info(&self, class_id: ClassId, token_id: TokenId) -> Result<Option<TokenInfo<<<Block as BlockT>::Header as HeaderT>::Number>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.token_info(&at, class_id, token_id).map_err(runtime_error).map(|token| token.map(|token| TokenInfo {
+			metadata: token.metadata.into(),
+			quantity: token.quantity,
+			deposit: format!("{}", token.data.deposit),
+			create_block: token.data.create_block,
+		}))
+	}
+
+	fn orders_of_token(&self, class_id: ClassId, token_id: TokenId, page: u32) -> Result<Vec<Order<AccountId, <<Block as BlockT>::Header as HeaderT>::Number>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.orders_of_token(&at, class_id, token_id, page, ORDERS_PAGE_SIZE).map_err(runtime_error)
+			.map(|orders| orders.into_iter().map(Into::into).collect())
+	}
+
+	fn orders_by_account(&self, who: AccountId, page: u32) -> Result<Vec<Order<AccountId, <<Block as BlockT>::Header as HeaderT>::Number>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.orders_by_account(&at, who, page, ORDERS_PAGE_SIZE).map_err(runtime_error)
+			.map(|orders| orders.into_iter().map(Into::into).collect())
+	}
+
+	fn orders_of_category(&self, category_id: CategoryId, page: u32) -> Result<Vec<Order<AccountId, <<Block as BlockT>::Header as HeaderT>::Number>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.orders_of_category(&at, category_id, page, ORDERS_PAGE_SIZE).map_err(runtime_error)
+			.map(|orders| orders.into_iter().map(Into::into).collect())
+	}
+
+	fn categories(&self, page: u32) -> Result<Vec<Category>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.categories(&at, page, CATEGORIES_PAGE_SIZE).map_err(runtime_error).map(|categories| {
+			categories.into_iter().map(|(category_id, category)| Category {
+				category_id,
+				metadata: category.metadata.into(),
+				nft_count: format!("{}", category.nft_count),
+			}).collect()
+		})
+	}
 }
@@ -0,0 +1,639 @@
+// Benchmarks for nftmart-nft Pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account, impl_benchmark_test_suite};
+use sp_core::{constants_types::{CurrencyId, NATIVE_CURRENCY_ID}, crypto::KeyTypeId, sr25519};
+use sp_std::{vec, prelude::*};
+use crate::Pallet as Nftmart;
+
+const SEED: u32 = 0;
+const UNIT: Balance = 1_000_000_000_000_000_000_000_000;
+const PRICE: Balance = 1_000_000_000_000_000_000;
+const MAX_METADATA_LEN: u32 = 1024;
+const MAX_QUANTITY: u32 = 1000;
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"nftm");
+
+fn native_currency<T: Config>() -> CurrencyIdOf<T> where CurrencyIdOf<T>: From<CurrencyId> {
+	NATIVE_CURRENCY_ID.into()
+}
+
+fn deadline<T: Config>() -> BlockNumberOf<T> {
+	frame_system::Pallet::<T>::block_number().saturating_add(100u32.into())
+}
+
+fn fund<T: Config>(who: &T::AccountId) {
+	<T as Config>::Currency::make_free_balance_be(who, UNIT.saturated_into());
+}
+
+/// A funded account in the whitelist.
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	fund::<T>(&who);
+	nftmart_config::AccountWhitelist::<T>::insert(&who, ());
+	who
+}
+
+/// Create a class by `creator`, return the class id and the class owner.
+fn create_class<T: Config>(creator: &T::AccountId, properties: Properties) -> Result<(ClassIdOf<T>, T::AccountId), &'static str> {
+	let class_id = orml_nft::Module::<T>::next_class_id();
+	Nftmart::<T>::create_class(
+		RawOrigin::Signed(creator.clone()).into(),
+		vec![1], vec![1], vec![1],
+		properties,
+		Perbill::zero(),
+		None, None,
+	)?;
+	let owner: T::AccountId = T::ModuleId::get().into_sub_account(class_id);
+	fund::<T>(&owner);
+	Ok((class_id, owner))
+}
+
+fn default_class<T: Config>(creator: &T::AccountId) -> Result<(ClassIdOf<T>, T::AccountId), &'static str> {
+	create_class::<T>(creator, Properties(ClassProperty::Transferable | ClassProperty::Burnable))
+}
+
+fn mint_token<T: Config>(
+	owner: &T::AccountId,
+	class_id: ClassIdOf<T>,
+	to: &T::AccountId,
+	quantity: QuantityOf<T>,
+) -> Result<TokenIdOf<T>, &'static str> {
+	let token_id = orml_nft::Module::<T>::next_token_id(class_id);
+	Nftmart::<T>::mint(RawOrigin::Signed(owner.clone()).into(), T::Lookup::unlookup(to.clone()), class_id, vec![1], quantity)?;
+	Ok(token_id)
+}
+
+/// Create a class and mint a token of it to `to`.
+fn class_with_token<T: Config>(to: &T::AccountId) -> Result<(ClassIdOf<T>, T::AccountId, TokenIdOf<T>), &'static str> {
+	let (class_id, owner) = default_class::<T>(to)?;
+	let token_id = mint_token::<T>(&owner, class_id, to, 1)?;
+	Ok((class_id, owner, token_id))
+}
+
+fn create_category<T: Config>() -> Result<CategoryIdOf<T>, &'static str> {
+	let category_id = Nftmart::<T>::next_category_id();
+	Nftmart::<T>::create_category(RawOrigin::Root.into(), vec![1])?;
+	Ok(category_id)
+}
+
+/// Create a bundle of `n` tokens owned by `who`.
+fn bundle<T: Config>(who: &T::AccountId, n: u32) -> Result<Vec<(ClassIdOf<T>, TokenIdOf<T>)>, &'static str> {
+	let (class_id, owner) = default_class::<T>(who)?;
+	let mut tokens = Vec::new();
+	for _ in 0..n {
+		tokens.push((class_id, mint_token::<T>(&owner, class_id, who, 1)?));
+	}
+	Ok(tokens)
+}
+
+benchmarks! {
+	where_clause { where
+		CurrencyIdOf<T>: From<CurrencyId>,
+		T::OffchainSignature: From<sr25519::Signature>,
+		T::OffchainPublic: From<sr25519::Public>,
+	}
+
+	create_category {
+		let m in 1 .. MAX_METADATA_LEN;
+	}: _(RawOrigin::Root, vec![0u8; m as usize])
+	verify {
+		assert!(Nftmart::<T>::categories(CategoryIdOf::<T>::zero()).is_some());
+	}
+
+	update_category {
+		let m in 1 .. MAX_METADATA_LEN;
+		let category_id = create_category::<T>()?;
+	}: _(RawOrigin::Root, category_id, vec![0u8; m as usize])
+
+	update_min_order_deposit {
+	}: _(RawOrigin::Root, PRICE)
+
+	update_platform_fee_rate {
+	}: _(RawOrigin::Root, Perbill::from_percent(1))
+
+	create_class {
+		let m in 1 .. MAX_METADATA_LEN;
+		let caller = funded_account::<T>("caller", 0);
+		let properties = Properties(ClassProperty::Transferable | ClassProperty::Burnable);
+	}: _(RawOrigin::Signed(caller), vec![0u8; m as usize], vec![0u8; 20], vec![0u8; 256], properties, Perbill::from_percent(5), Some(MAX_QUANTITY), Some(MAX_QUANTITY))
+	verify {
+		assert!(orml_nft::Module::<T>::classes(ClassIdOf::<T>::zero()).is_some());
+	}
+
+	update_royalty_beneficiary {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+	}: _(RawOrigin::Signed(owner), class_id, T::Lookup::unlookup(caller))
+
+	update_class {
+		let m in 1 .. MAX_METADATA_LEN;
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+	}: _(RawOrigin::Signed(owner), class_id, vec![0u8; m as usize], vec![0u8; 20], vec![0u8; 256])
+
+	update_token_metadata {
+		let m in 1 .. MAX_METADATA_LEN;
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner, token_id) = class_with_token::<T>(&caller)?;
+	}: _(RawOrigin::Signed(owner), class_id, token_id, vec![0u8; m as usize])
+
+	freeze_metadata {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+	}: _(RawOrigin::Signed(owner), class_id)
+
+	set_class_attribute {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+		let key = vec![0u8; T::MaxAttributeKeyLength::get() as usize];
+	}: _(RawOrigin::Signed(owner), class_id, key, AttributeValue::Integer(1))
+
+	clear_class_attribute {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+		let key = vec![0u8; T::MaxAttributeKeyLength::get() as usize];
+		Nftmart::<T>::set_class_attribute(RawOrigin::Signed(owner.clone()).into(), class_id, key.clone(), AttributeValue::Integer(1))?;
+	}: _(RawOrigin::Signed(owner), class_id, key)
+
+	set_token_attribute {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner, token_id) = class_with_token::<T>(&caller)?;
+		let key = vec![0u8; T::MaxAttributeKeyLength::get() as usize];
+	}: _(RawOrigin::Signed(owner), class_id, token_id, key, AttributeValue::Integer(1))
+
+	clear_token_attribute {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner, token_id) = class_with_token::<T>(&caller)?;
+		let key = vec![0u8; T::MaxAttributeKeyLength::get() as usize];
+		Nftmart::<T>::set_token_attribute(RawOrigin::Signed(owner.clone()).into(), class_id, token_id, key.clone(), AttributeValue::Integer(1))?;
+	}: _(RawOrigin::Signed(owner), class_id, token_id, key)
+
+	set_class_currencies {
+		let n in 1 .. T::MaxClassCurrencies::get();
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+		let currencies: Vec<CurrencyIdOf<T>> = (0..n).map(|i| CurrencyIdOf::<T>::from(i + 1)).collect();
+		for currency_id in currencies.iter() {
+			nftmart_config::AcceptedCurrencies::<T>::insert(currency_id, 0);
+		}
+	}: _(RawOrigin::Signed(owner), class_id, currencies)
+	verify {
+		assert!(Nftmart::<T>::class_currencies(class_id).is_some());
+	}
+
+	revoke {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = create_class::<T>(&caller, Properties(ClassProperty::Revocable.into()))?;
+		let token_id = mint_token::<T>(&owner, class_id, &caller, 1)?;
+		let key = vec![0u8; T::MaxAttributeKeyLength::get() as usize];
+		Nftmart::<T>::set_token_attribute(RawOrigin::Signed(owner.clone()).into(), class_id, token_id, key, AttributeValue::Integer(1))?;
+	}: _(RawOrigin::Signed(owner), class_id, token_id, T::Lookup::unlookup(caller), One::one())
+	verify {
+		assert!(orml_nft::Module::<T>::tokens(class_id, token_id).is_none());
+	}
+
+	reassign {
+		let caller = funded_account::<T>("caller", 0);
+		let to = funded_account::<T>("to", 0);
+		let (class_id, owner) = create_class::<T>(&caller, Properties(ClassProperty::Revocable.into()))?;
+		let token_id = mint_token::<T>(&owner, class_id, &caller, 1)?;
+	}: _(RawOrigin::Signed(owner), class_id, token_id, T::Lookup::unlookup(caller), T::Lookup::unlookup(to.clone()), One::one())
+	verify {
+		assert_eq!(orml_nft::Module::<T>::owner_of((class_id, token_id)), Some(to));
+	}
+
+	add_class_admin {
+		let caller = funded_account::<T>("caller", 0);
+		let admin: T::AccountId = account("admin", 0, SEED);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+	}: _(RawOrigin::Signed(owner), class_id, T::Lookup::unlookup(admin), ClassAdminRole::Admin)
+
+	remove_class_admin {
+		let caller = funded_account::<T>("caller", 0);
+		let admin: T::AccountId = account("admin", 0, SEED);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+		Nftmart::<T>::add_class_admin(RawOrigin::Signed(owner.clone()).into(), class_id, T::Lookup::unlookup(admin.clone()), ClassAdminRole::Admin)?;
+	}: _(RawOrigin::Signed(owner), class_id, T::Lookup::unlookup(admin), ClassAdminRole::Admin)
+
+	// A token is minted with a single write whatever its quantity.
+	mint {
+		let m in 1 .. MAX_METADATA_LEN;
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+	}: _(RawOrigin::Signed(owner), T::Lookup::unlookup(caller.clone()), class_id, vec![0u8; m as usize], MAX_QUANTITY)
+	verify {
+		assert_eq!(orml_nft::Module::<T>::tokens_by_owner(&caller, (class_id, TokenIdOf::<T>::zero())), MAX_QUANTITY);
+	}
+
+	set_drop {
+		let m in 1 .. T::MaxDropMetadataLength::get();
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+		let drop = DropData {
+			currency_id: native_currency::<T>(),
+			price: PRICE,
+			start: Zero::zero(),
+			end: deadline::<T>(),
+			max_per_wallet: Some(MAX_QUANTITY),
+			allowlist: Some(H256::zero()),
+			metadata: vec![0u8; m as usize],
+		};
+	}: _(RawOrigin::Signed(owner), class_id, drop)
+
+	remove_drop {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+		let drop = DropData {
+			currency_id: native_currency::<T>(),
+			price: PRICE,
+			start: Zero::zero(),
+			end: deadline::<T>(),
+			max_per_wallet: None,
+			allowlist: None,
+			metadata: vec![1],
+		};
+		Nftmart::<T>::set_drop(RawOrigin::Signed(owner.clone()).into(), class_id, drop)?;
+	}: _(RawOrigin::Signed(owner), class_id)
+
+	redeem_voucher {
+		let m in 1 .. MAX_METADATA_LEN;
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+		let public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+		let signer = T::OffchainPublic::from(public).into_account();
+		fund::<T>(&signer);
+		Nftmart::<T>::add_class_admin(RawOrigin::Signed(owner.clone()).into(), class_id, T::Lookup::unlookup(signer.clone()), ClassAdminRole::Minter)?;
+		let metadata = vec![0u8; m as usize];
+		let voucher = MintVoucher {
+			signer,
+			class_id,
+			metadata_hash: BlakeTwo256::hash(&metadata),
+			quantity: 1,
+			currency_id: native_currency::<T>(),
+			price: PRICE,
+			nonce: 0,
+			expiry: deadline::<T>(),
+		};
+		let signature = sp_io::crypto::sr25519_sign(KEY_TYPE, &public, &Nftmart::<T>::voucher_payload(&voucher)).ok_or("failed to sign")?;
+	}: _(RawOrigin::Signed(caller), voucher, metadata, signature.into())
+	verify {
+		assert!(Nftmart::<T>::redeemed_vouchers(class_id, 0).is_some());
+	}
+
+	public_mint {
+		let p in 0 .. T::MaxProofLength::get();
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+		let proof: Vec<H256> = (0..p).map(|i| BlakeTwo256::hash_of(&i)).collect();
+		let root = proof.iter().fold(BlakeTwo256::hash_of(&caller), |hash, node| {
+			if hash <= *node {
+				BlakeTwo256::hash_of(&(hash, *node))
+			} else {
+				BlakeTwo256::hash_of(&(*node, hash))
+			}
+		});
+		let drop = DropData {
+			currency_id: native_currency::<T>(),
+			price: PRICE,
+			start: Zero::zero(),
+			end: deadline::<T>(),
+			max_per_wallet: Some(MAX_QUANTITY),
+			allowlist: Some(root),
+			metadata: vec![0u8; T::MaxDropMetadataLength::get() as usize],
+		};
+		Nftmart::<T>::set_drop(RawOrigin::Signed(owner).into(), class_id, drop)?;
+	}: _(RawOrigin::Signed(caller.clone()), class_id, MAX_QUANTITY, proof)
+	verify {
+		assert_eq!(Nftmart::<T>::drop_minted(class_id, &caller), MAX_QUANTITY);
+	}
+
+	transfer {
+		let caller = funded_account::<T>("caller", 0);
+		let to: T::AccountId = account("to", 0, SEED);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller), T::Lookup::unlookup(to.clone()), class_id, token_id, One::one())
+	verify {
+		assert_eq!(orml_nft::Module::<T>::owner_of((class_id, token_id)), Some(to));
+	}
+
+	transfer_from {
+		let caller = funded_account::<T>("caller", 0);
+		let owner = funded_account::<T>("owner", 0);
+		let to: T::AccountId = account("to", 0, SEED);
+		let (class_id, _, token_id) = class_with_token::<T>(&owner)?;
+		Nftmart::<T>::approve(RawOrigin::Signed(owner.clone()).into(), Some(T::Lookup::unlookup(caller.clone())), class_id, token_id)?;
+	}: _(RawOrigin::Signed(caller), T::Lookup::unlookup(owner), T::Lookup::unlookup(to.clone()), class_id, token_id, One::one())
+	verify {
+		assert_eq!(orml_nft::Module::<T>::owner_of((class_id, token_id)), Some(to));
+	}
+
+	approve {
+		let caller = funded_account::<T>("caller", 0);
+		let spender: T::AccountId = account("spender", 0, SEED);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller), Some(T::Lookup::unlookup(spender)), class_id, token_id)
+
+	set_approval_for_all {
+		let caller = funded_account::<T>("caller", 0);
+		let operator: T::AccountId = account("operator", 0, SEED);
+	}: _(RawOrigin::Signed(caller), T::Lookup::unlookup(operator), true)
+
+	burn {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner, token_id) = class_with_token::<T>(&caller)?;
+		let key = vec![0u8; T::MaxAttributeKeyLength::get() as usize];
+		Nftmart::<T>::set_token_attribute(RawOrigin::Signed(owner).into(), class_id, token_id, key, AttributeValue::Integer(1))?;
+	}: _(RawOrigin::Signed(caller), class_id, token_id, One::one())
+	verify {
+		assert!(orml_nft::Module::<T>::tokens(class_id, token_id).is_none());
+	}
+
+	destroy_class {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner) = default_class::<T>(&caller)?;
+	}: _(RawOrigin::Signed(owner), class_id, T::Lookup::unlookup(caller))
+	verify {
+		assert!(orml_nft::Module::<T>::classes(class_id).is_none());
+	}
+
+	submit_order {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+		let category_id = create_category::<T>()?;
+	}: _(RawOrigin::Signed(caller.clone()), native_currency::<T>(), PRICE, category_id, class_id, token_id, One::one(), Nftmart::<T>::min_order_deposit(), deadline::<T>())
+	verify {
+		assert!(Nftmart::<T>::orders((class_id, token_id), &caller).is_some());
+	}
+
+	submit_escrowed_order {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+		let category_id = create_category::<T>()?;
+	}: _(RawOrigin::Signed(caller.clone()), native_currency::<T>(), PRICE, category_id, class_id, token_id, One::one(), Nftmart::<T>::min_order_deposit(), deadline::<T>())
+	verify {
+		assert_eq!(orml_nft::Module::<T>::owner_of((class_id, token_id)), Some(Nftmart::<T>::order_escrow_account()));
+	}
+
+	take_order {
+		let caller = funded_account::<T>("caller", 0);
+		let seller = funded_account::<T>("seller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&seller)?;
+		let category_id = create_category::<T>()?;
+		Nftmart::<T>::submit_order(
+			RawOrigin::Signed(seller.clone()).into(),
+			native_currency::<T>(), PRICE, category_id, class_id, token_id, One::one(),
+			Nftmart::<T>::min_order_deposit(), deadline::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), class_id, token_id, PRICE, seller)
+	verify {
+		assert_eq!(orml_nft::Module::<T>::owner_of((class_id, token_id)), Some(caller));
+	}
+
+	remove_order {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+		let category_id = create_category::<T>()?;
+		Nftmart::<T>::submit_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			native_currency::<T>(), PRICE, category_id, class_id, token_id, One::one(),
+			Nftmart::<T>::min_order_deposit(), deadline::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), class_id, token_id)
+	verify {
+		assert!(Nftmart::<T>::orders((class_id, token_id), &caller).is_none());
+	}
+
+	update_order_price {
+		let caller = funded_account::<T>("caller", 0);
+		let seller = funded_account::<T>("seller", 0);
+		let (class_id, owner) = create_class::<T>(&seller, Properties(ClassProperty::Transferable.into()))?;
+		let token_id = mint_token::<T>(&owner, class_id, &seller, 1)?;
+		let category_id = create_category::<T>()?;
+		// A bid reserves the price, which is the worse case.
+		Nftmart::<T>::submit_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			native_currency::<T>(), PRICE, category_id, class_id, token_id, One::one(),
+			Nftmart::<T>::min_order_deposit(), deadline::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller), class_id, token_id, PRICE.saturating_mul(2))
+
+	submit_offer {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _) = default_class::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller), native_currency::<T>(), class_id, PRICE, MAX_QUANTITY, Nftmart::<T>::min_order_deposit(), deadline::<T>())
+
+	fill_offer {
+		let caller = funded_account::<T>("caller", 0);
+		let buyer = funded_account::<T>("buyer", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+		let offer_id = Nftmart::<T>::next_id();
+		Nftmart::<T>::submit_offer(
+			RawOrigin::Signed(buyer.clone()).into(),
+			native_currency::<T>(), class_id, PRICE, 1, Nftmart::<T>::min_order_deposit(), deadline::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller), class_id, offer_id, token_id, PRICE)
+	verify {
+		assert_eq!(orml_nft::Module::<T>::owner_of((class_id, token_id)), Some(buyer));
+	}
+
+	remove_offer {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _) = default_class::<T>(&caller)?;
+		let offer_id = Nftmart::<T>::next_id();
+		Nftmart::<T>::submit_offer(
+			RawOrigin::Signed(caller.clone()).into(),
+			native_currency::<T>(), class_id, PRICE, MAX_QUANTITY, Nftmart::<T>::min_order_deposit(), deadline::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller), class_id, offer_id)
+
+	submit_bundle_order {
+		let n in 2 .. T::MaxBundleSize::get();
+		let caller = funded_account::<T>("caller", 0);
+		let tokens = bundle::<T>(&caller, n)?;
+		let category_id = create_category::<T>()?;
+	}: _(RawOrigin::Signed(caller), native_currency::<T>(), category_id, PRICE, tokens, Nftmart::<T>::min_order_deposit(), deadline::<T>())
+
+	take_bundle_order {
+		let n in 2 .. T::MaxBundleSize::get();
+		let caller = funded_account::<T>("caller", 0);
+		let seller = funded_account::<T>("seller", 0);
+		let tokens = bundle::<T>(&seller, n)?;
+		let category_id = create_category::<T>()?;
+		let order_id = Nftmart::<T>::next_id();
+		Nftmart::<T>::submit_bundle_order(
+			RawOrigin::Signed(seller).into(),
+			native_currency::<T>(), category_id, PRICE, tokens, Nftmart::<T>::min_order_deposit(), deadline::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller), order_id, PRICE)
+	verify {
+		assert!(Nftmart::<T>::bundle_orders(order_id).is_none());
+	}
+
+	remove_bundle_order {
+		let n in 2 .. T::MaxBundleSize::get();
+		let caller = funded_account::<T>("caller", 0);
+		let tokens = bundle::<T>(&caller, n)?;
+		let category_id = create_category::<T>()?;
+		let order_id = Nftmart::<T>::next_id();
+		Nftmart::<T>::submit_bundle_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			native_currency::<T>(), category_id, PRICE, tokens, Nftmart::<T>::min_order_deposit(), deadline::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller), order_id)
+
+	submit_dutch_order {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+		let category_id = create_category::<T>()?;
+	}: _(
+		RawOrigin::Signed(caller), native_currency::<T>(), category_id, class_id, token_id,
+		PRICE, PRICE / 2, Nftmart::<T>::min_order_deposit(), Zero::zero(), deadline::<T>(), One::one()
+	)
+
+	take_dutch_order {
+		let caller = funded_account::<T>("caller", 0);
+		let seller = funded_account::<T>("seller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&seller)?;
+		let category_id = create_category::<T>()?;
+		Nftmart::<T>::submit_dutch_order(
+			RawOrigin::Signed(seller).into(),
+			native_currency::<T>(), category_id, class_id, token_id,
+			PRICE, PRICE / 2, Nftmart::<T>::min_order_deposit(), Zero::zero(), deadline::<T>(), One::one(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), class_id, token_id, PRICE)
+	verify {
+		assert_eq!(orml_nft::Module::<T>::owner_of((class_id, token_id)), Some(caller));
+	}
+
+	remove_dutch_order {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+		let category_id = create_category::<T>()?;
+		Nftmart::<T>::submit_dutch_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			native_currency::<T>(), category_id, class_id, token_id,
+			PRICE, PRICE / 2, Nftmart::<T>::min_order_deposit(), Zero::zero(), deadline::<T>(), One::one(),
+		)?;
+	}: _(RawOrigin::Signed(caller), class_id, token_id)
+
+	create_auction {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+	}: _(
+		RawOrigin::Signed(caller), native_currency::<T>(), class_id, token_id,
+		PRICE, PRICE / 10, Nftmart::<T>::min_order_deposit(), Zero::zero(), deadline::<T>()
+	)
+
+	bid {
+		let caller = funded_account::<T>("caller", 0);
+		let bidder = funded_account::<T>("bidder", 0);
+		let seller = funded_account::<T>("seller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&seller)?;
+		let auction_id = Nftmart::<T>::next_id();
+		Nftmart::<T>::create_auction(
+			RawOrigin::Signed(seller).into(),
+			native_currency::<T>(), class_id, token_id,
+			PRICE, PRICE / 10, Nftmart::<T>::min_order_deposit(), Zero::zero(), deadline::<T>(),
+		)?;
+		// Outbidding refunds the previous bidder, which is the worse case.
+		Nftmart::<T>::bid(RawOrigin::Signed(bidder).into(), auction_id, PRICE)?;
+	}: _(RawOrigin::Signed(caller), auction_id, PRICE.saturating_mul(2))
+
+	cancel_auction {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+		let auction_id = Nftmart::<T>::next_id();
+		Nftmart::<T>::create_auction(
+			RawOrigin::Signed(caller.clone()).into(),
+			native_currency::<T>(), class_id, token_id,
+			PRICE, PRICE / 10, Nftmart::<T>::min_order_deposit(), Zero::zero(), deadline::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller), auction_id)
+
+	list_rental {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller), class_id, token_id, native_currency::<T>(), PRICE, 100u32.into())
+
+	unlist_rental {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+		Nftmart::<T>::list_rental(RawOrigin::Signed(caller.clone()).into(), class_id, token_id, native_currency::<T>(), PRICE, 100u32.into())?;
+	}: _(RawOrigin::Signed(caller), class_id, token_id)
+
+	rent {
+		let caller = funded_account::<T>("caller", 0);
+		let lender = funded_account::<T>("lender", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&lender)?;
+		Nftmart::<T>::list_rental(RawOrigin::Signed(lender).into(), class_id, token_id, native_currency::<T>(), PRICE, 100u32.into())?;
+	}: _(RawOrigin::Signed(caller.clone()), class_id, token_id, 100u32.into())
+	verify {
+		assert_eq!(Nftmart::<T>::user_of(class_id, token_id), Some(caller));
+	}
+
+	fractionalize {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller), class_id, token_id, PRICE, native_currency::<T>(), PRICE)
+	verify {
+		assert!(Nftmart::<T>::vaults((class_id, token_id)).is_some());
+	}
+
+	redeem_fractions {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+		Nftmart::<T>::fractionalize(RawOrigin::Signed(caller.clone()).into(), class_id, token_id, PRICE, native_currency::<T>(), PRICE)?;
+	}: _(RawOrigin::Signed(caller), class_id, token_id)
+	verify {
+		assert!(Nftmart::<T>::vaults((class_id, token_id)).is_none());
+	}
+
+	buyout {
+		let caller = funded_account::<T>("caller", 0);
+		let seller = funded_account::<T>("seller", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&seller)?;
+		Nftmart::<T>::fractionalize(RawOrigin::Signed(seller).into(), class_id, token_id, PRICE, native_currency::<T>(), PRICE)?;
+	}: _(RawOrigin::Signed(caller.clone()), class_id, token_id)
+	verify {
+		assert_eq!(orml_nft::Module::<T>::owner_of((class_id, token_id)), Some(caller));
+	}
+
+	claim_proceeds {
+		let caller = funded_account::<T>("caller", 0);
+		let buyer = funded_account::<T>("buyer", 0);
+		let (class_id, _, token_id) = class_with_token::<T>(&caller)?;
+		Nftmart::<T>::fractionalize(RawOrigin::Signed(caller.clone()).into(), class_id, token_id, PRICE, native_currency::<T>(), PRICE)?;
+		Nftmart::<T>::buyout(RawOrigin::Signed(buyer).into(), class_id, token_id)?;
+	}: _(RawOrigin::Signed(caller), class_id, token_id)
+	verify {
+		assert!(Nftmart::<T>::vaults((class_id, token_id)).is_none());
+	}
+
+	nest {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner, parent_token_id) = class_with_token::<T>(&caller)?;
+		let token_id = mint_token::<T>(&owner, class_id, &caller, 1)?;
+	}: _(RawOrigin::Signed(caller), class_id, token_id, class_id, parent_token_id)
+	verify {
+		assert_eq!(orml_nft::Module::<T>::parents((class_id, token_id)), Some((class_id, parent_token_id)));
+	}
+
+	unnest {
+		let caller = funded_account::<T>("caller", 0);
+		let (class_id, owner, parent_token_id) = class_with_token::<T>(&caller)?;
+		let token_id = mint_token::<T>(&owner, class_id, &caller, 1)?;
+		Nftmart::<T>::nest(RawOrigin::Signed(caller.clone()).into(), class_id, token_id, class_id, parent_token_id)?;
+	}: _(RawOrigin::Signed(caller), class_id, token_id)
+	verify {
+		assert!(orml_nft::Module::<T>::parents((class_id, token_id)).is_none());
+	}
+}
+
+impl_benchmark_test_suite!(
+	Nftmart,
+	crate::mock::ExtBuilder::default().build(),
+	crate::mock::Runtime,
+);
@@ -8,21 +8,27 @@ use frame_support::{
 };
 use sp_std::vec::Vec;
 use frame_system::pallet_prelude::*;
-use orml_traits::{MultiCurrency, MultiReservableCurrency};
-use sp_core::constants_types::{Balance, ACCURACY};
+use orml_traits::{
+	Auction, AuctionHandler, AuctionInfo, Change, CreateCurrency, MultiCurrency, MultiReservableCurrency, OnNewBidResult,
+};
+use sp_core::{constants_types::{Balance, GlobalId, Quantity, ACCURACY}, H256};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
-	traits::{CheckedAdd, Bounded,
+	traits::{CheckedAdd, Bounded, Saturating, Convert, BlakeTwo256, Hash, Verify, IdentifyAccount,
 			 AccountIdConversion, StaticLookup, Zero, One, AtLeast32BitUnsigned},
-	ModuleId, RuntimeDebug, SaturatedConversion,
+	helpers_128bit::multiply_by_rational,
+	ModuleId, Perbill, RuntimeDebug, SaturatedConversion,
 };
 use codec::FullCodec;
 
 mod mock;
 mod tests;
+mod benchmarking;
+pub mod weights;
 
 pub use module::*;
+pub use weights::WeightInfo;
 
 #[repr(u8)]
 #[derive(Encode, Decode, Clone, Copy, BitFlags, RuntimeDebug, PartialEq, Eq)]
@@ -31,6 +37,35 @@ pub enum ClassProperty {
 	Transferable = 0b00000001,
 	/// Token can be burned
 	Burnable = 0b00000010,
+	/// Metadata of the class and its tokens can not be updated any more
+	MetadataFrozen = 0b00000100,
+	/// Tokens can be revoked or reassigned by the class owner, only for non-transferable classes
+	Revocable = 0b00001000,
+}
+
+/// The role granted to an administrator of a class.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum ClassAdminRole {
+	/// Can mint, manage metadata, attributes, admins, drops and currencies of the class,
+	/// and revoke or reassign its tokens. Can not destroy the class, change its royalty
+	/// beneficiary, nor move tokens held by the class account.
+	Admin,
+	/// Can only mint tokens of the class.
+	Minter,
+	/// Can only update or freeze metadata of the class and its tokens.
+	MetadataEditor,
+}
+
+/// The value of an on-chain attribute of a class or a token.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum AttributeValue {
+	/// A flag, e.g. `soulbound`
+	Bool(bool),
+	/// A number, e.g. `level`
+	Integer(u64),
+	/// Raw bytes, e.g. `rarity`
+	Bytes(Vec<u8>),
 }
 
 #[derive(Clone, Copy, PartialEq, Default, RuntimeDebug)]
@@ -66,6 +101,12 @@ pub struct ClassData<BlockNumber> {
 	pub description: Vec<u8>,
 	#[codec(compact)]
 	pub create_block: BlockNumber,
+	/// The royalty paid to the class beneficiary on every trade.
+	pub royalty_rate: Perbill,
+	/// The maximum quantity of tokens ever minted, burned tokens included, unlimited if `None`.
+	pub max_supply: Option<Quantity>,
+	/// The maximum quantity of tokens minted to a single account, unlimited if `None`.
+	pub max_mint_per_account: Option<Quantity>,
 }
 
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
@@ -83,7 +124,7 @@ pub struct TokenData<BlockNumber> {
 pub struct CategoryData {
 	/// The category metadata.
 	pub metadata: NFTMetadata,
-	/// The number of NFTs in this category.
+	/// The number of orders in this category, dutch and bundle orders are not counted.
 	#[codec(compact)]
 	pub nft_count: Balance,
 }
@@ -106,31 +147,323 @@ pub struct OrderData<T: Config> {
 	/// Category of this order.
 	#[codec(compact)]
 	pub category_id: CategoryIdOf<T>,
-	// TODO: Add `is_token_owner` field.
+	/// The quantity of the token to trade, `price` is paid for the whole quantity.
+	#[codec(compact)]
+	pub quantity: QuantityOf<T>,
+	/// A token owner's order sells the token, otherwise it is a bid.
+	pub by_token_owner: bool,
+}
+
+/// An order together with the token and account it belongs to, as returned by the runtime API.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct OrderInfo<AccountId, ClassId, TokenId, Quantity, CurrencyId, CategoryId, BlockNumber> {
+	/// The class of the token.
+	pub class_id: ClassId,
+	/// The token being traded.
+	pub token_id: TokenId,
+	/// The account who placed this order.
+	pub owner: AccountId,
+	/// currency ID.
+	pub currency_id: CurrencyId,
+	/// Price of this order.
+	pub price: Balance,
+	/// The balances to create an order
+	pub deposit: Balance,
+	/// This order will be invalidated after `deadline` block number.
+	pub deadline: BlockNumber,
+	/// Category of this order.
+	pub category_id: CategoryId,
+	/// The quantity of the token to trade.
+	pub quantity: Quantity,
+	/// A token owner's order sells the token, otherwise it is a bid.
+	pub by_token_owner: bool,
+}
+
+/// An order indexed by its deadline, removed by `on_initialize` once the deadline has passed.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub enum ExpiringOrder<AccountId, ClassId, TokenId> {
+	/// An order of `Orders`, by token and owner.
+	Order((ClassId, TokenId), AccountId),
+	/// A dutch order, by token. It expires after its `end_block`.
+	Dutch((ClassId, TokenId)),
+	/// A bundle order, by order id.
+	Bundle(GlobalId),
+	/// A collection offer, by class and offer id.
+	Offer(ClassId, GlobalId),
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AuctionItem<T: Config> {
+	/// The seller of this auction.
+	pub owner: T::AccountId,
+	/// currency ID.
+	#[codec(compact)]
+	pub currency_id: CurrencyIdOf<T>,
+	/// Class ID of the escrowed NFT.
+	#[codec(compact)]
+	pub class_id: ClassIdOf<T>,
+	/// Token ID of the escrowed NFT.
+	#[codec(compact)]
+	pub token_id: TokenIdOf<T>,
+	/// The first bid must be at least `reserve_price`.
+	#[codec(compact)]
+	pub reserve_price: Balance,
+	/// Every following bid must exceed the previous one by at least `min_raise`.
+	#[codec(compact)]
+	pub min_raise: Balance,
+	/// The balances to create an auction
+	#[codec(compact)]
+	pub deposit: Balance,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DutchOrderData<T: Config> {
+	/// The seller of this order.
+	pub owner: T::AccountId,
+	/// currency ID.
+	#[codec(compact)]
+	pub currency_id: CurrencyIdOf<T>,
+	/// The price at `start_block`.
+	#[codec(compact)]
+	pub start_price: Balance,
+	/// The price at `end_block`.
+	#[codec(compact)]
+	pub floor_price: Balance,
+	/// The price begins to decay since `start_block`.
+	#[codec(compact)]
+	pub start_block: BlockNumberOf<T>,
+	/// The price reaches `floor_price` at `end_block`, the order expires after it.
+	#[codec(compact)]
+	pub end_block: BlockNumberOf<T>,
+	/// The price drops every `step` blocks. `1` means linear decay.
+	#[codec(compact)]
+	pub step: BlockNumberOf<T>,
+	/// The balances to create an order
+	#[codec(compact)]
+	pub deposit: Balance,
+	/// Category of this order.
+	#[codec(compact)]
+	pub category_id: CategoryIdOf<T>,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct OfferData<T: Config> {
+	/// The buyer of this offer.
+	pub owner: T::AccountId,
+	/// currency ID.
+	#[codec(compact)]
+	pub currency_id: CurrencyIdOf<T>,
+	/// Price of every token.
+	#[codec(compact)]
+	pub price: Balance,
+	/// The number of tokens still wanted.
+	#[codec(compact)]
+	pub quantity: QuantityOf<T>,
+	/// The balances to create an offer
+	#[codec(compact)]
+	pub deposit: Balance,
+	/// This offer will be invalidated after `deadline` block number.
+	#[codec(compact)]
+	pub deadline: BlockNumberOf<T>,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BundleOrderData<T: Config> {
+	/// The seller of this bundle.
+	pub owner: T::AccountId,
+	/// currency ID.
+	#[codec(compact)]
+	pub currency_id: CurrencyIdOf<T>,
+	/// Price of the whole bundle.
+	#[codec(compact)]
+	pub price: Balance,
+	/// The balances to create an order
+	#[codec(compact)]
+	pub deposit: Balance,
+	/// This order will be invalidated after `deadline` block number.
+	#[codec(compact)]
+	pub deadline: BlockNumberOf<T>,
+	/// Category of this order.
+	#[codec(compact)]
+	pub category_id: CategoryIdOf<T>,
+	/// Tokens sold together.
+	pub tokens: Vec<(ClassIdOf<T>, TokenIdOf<T>)>,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DropData<T: Config> {
+	/// currency ID.
+	#[codec(compact)]
+	pub currency_id: CurrencyIdOf<T>,
+	/// Price of a single token.
+	#[codec(compact)]
+	pub price: Balance,
+	/// The first block of the sale.
+	#[codec(compact)]
+	pub start: BlockNumberOf<T>,
+	/// The sale is closed from `end` block number.
+	#[codec(compact)]
+	pub end: BlockNumberOf<T>,
+	/// The maximum quantity minted by a single account, unlimited if `None`.
+	pub max_per_wallet: Option<u32>,
+	/// Merkle root of the accounts allowed to mint, anyone can mint if `None`.
+	pub allowlist: Option<H256>,
+	/// Metadata of every minted token.
+	pub metadata: NFTMetadata,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RentalListing<T: Config> {
+	/// The lender, holding the whole quantity of the token.
+	pub owner: T::AccountId,
+	/// currency ID.
+	#[codec(compact)]
+	pub currency_id: CurrencyIdOf<T>,
+	/// Rent paid for every block.
+	#[codec(compact)]
+	pub price_per_block: Balance,
+	/// The maximum number of blocks of a single rental.
+	#[codec(compact)]
+	pub max_duration: BlockNumberOf<T>,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RentalData<T: Config> {
+	/// The lender, still owning the token.
+	pub lender: T::AccountId,
+	/// The account allowed to use the token.
+	pub user: T::AccountId,
+	/// The usage right expires at `end` block number.
+	#[codec(compact)]
+	pub end: BlockNumberOf<T>,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VaultData<T: Config> {
+	/// The account fractionalized the token.
+	pub owner: T::AccountId,
+	/// The token quantity locked in the vault.
+	#[codec(compact)]
+	pub quantity: QuantityOf<T>,
+	/// The currency of shares.
+	#[codec(compact)]
+	pub share_currency_id: CurrencyIdOf<T>,
+	/// Shares in circulation.
+	#[codec(compact)]
+	pub shares: Balance,
+	/// The currency of the reserve price.
+	#[codec(compact)]
+	pub currency_id: CurrencyIdOf<T>,
+	/// Anyone paying the reserve price buys the token out.
+	#[codec(compact)]
+	pub reserve_price: Balance,
+	/// Proceeds of the buyout not claimed yet, `None` before the buyout.
+	pub proceeds: Option<Balance>,
+}
+
+/// The account holding the tokens nested into a token.
+pub struct TokenAccount<T>(PhantomData<T>);
+
+impl<T: Config> Convert<(ClassIdOf<T>, TokenIdOf<T>), T::AccountId> for TokenAccount<T> {
+	fn convert(token: (ClassIdOf<T>, TokenIdOf<T>)) -> T::AccountId {
+		T::ModuleId::get().into_sub_account((*b"nest", token))
+	}
+}
+
+/// A voucher signed off-chain by a minter of a class, anyone can redeem it to mint the token.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct MintVoucher<T: Config> {
+	/// The account signing this voucher, it must be a minter of the class.
+	pub signer: T::AccountId,
+	/// class ID.
+	#[codec(compact)]
+	pub class_id: ClassIdOf<T>,
+	/// `BlakeTwo256` hash of the metadata of the token.
+	pub metadata_hash: H256,
+	/// The token quantity.
+	#[codec(compact)]
+	pub quantity: QuantityOf<T>,
+	/// currency ID.
+	#[codec(compact)]
+	pub currency_id: CurrencyIdOf<T>,
+	/// Price of the whole quantity, paid to the signer.
+	#[codec(compact)]
+	pub price: Balance,
+	/// A voucher of a class can be redeemed once per nonce.
+	#[codec(compact)]
+	pub nonce: u64,
+	/// This voucher will be invalidated after `expiry` block number.
+	#[codec(compact)]
+	pub expiry: BlockNumberOf<T>,
+}
+
+impl<T: Config> DutchOrderData<T> {
+	/// The price to take this order at block `now`.
+	pub fn price_at(&self, now: BlockNumberOf<T>) -> Balance {
+		if now <= self.start_block {
+			return self.start_price;
+		}
+		if now >= self.end_block {
+			return self.floor_price;
+		}
+		let step = self.step.max(One::one());
+		let elapsed: u128 = ((now - self.start_block) / step * step).saturated_into();
+		let duration: u128 = (self.end_block - self.start_block).saturated_into();
+		let max_drop = self.start_price.saturating_sub(self.floor_price);
+		let dropped = multiply_by_rational(max_drop, elapsed, duration).unwrap_or(max_drop);
+		self.start_price.saturating_sub(dropped)
+	}
 }
 
 pub type NFTMetadata = Vec<u8>;
 pub type TokenIdOf<T> = <T as orml_nft::Config>::TokenId;
+pub type QuantityOf<T> = <T as orml_nft::Config>::Quantity;
 pub type ClassIdOf<T> = <T as orml_nft::Config>::ClassId;
 pub type CategoryIdOf<T> = <T as Config>::CategoryId;
 pub type BalanceOf<T> = <<T as module::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 pub type CurrencyIdOf<T> = <<T as module::Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
 pub type BlockNumberOf<T> = <T as frame_system::Config>::BlockNumber;
+pub type OrderInfoOf<T> = OrderInfo<
+	<T as frame_system::Config>::AccountId, ClassIdOf<T>, TokenIdOf<T>, QuantityOf<T>, CurrencyIdOf<T>, CategoryIdOf<T>, BlockNumberOf<T>,
+>;
+pub type ExpiringOrderOf<T> = ExpiringOrder<<T as frame_system::Config>::AccountId, ClassIdOf<T>, TokenIdOf<T>>;
 
 #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
 enum Releases {
 	V1_0_0,
 	V2_0_0,
+	V3_0_0,
+	V4_0_0,
+	V5_0_0,
+	V6_0_0,
+	V7_0_0,
+	V8_0_0,
+	V9_0_0,
+	V10_0_0,
 }
 
 impl Default for Releases {
 	fn default() -> Self {
-		Releases::V2_0_0
+		Releases::V10_0_0
 	}
 }
 
 pub mod migrations {
 	use super::*;
+	use frame_support::storage::{
+		migration::{put_storage_value, StorageIterator, StorageKeyIterator},
+		StoragePrefixedMap,
+	};
 
 	#[derive(Decode)]
 	pub struct OldClassData {
@@ -148,18 +481,95 @@ pub mod migrations {
 	}
 
 	impl OldClassData {
-		fn upgraded<T>(self) -> ClassData<T> where T: AtLeast32BitUnsigned + Bounded + Copy + From<u32> {
+		fn upgraded<T>(self) -> ClassDataV5<T> where T: AtLeast32BitUnsigned + Bounded + Copy + From<u32> {
 			let create_block: T = One::one();
-			ClassData {
+			ClassDataV5 {
 				create_block: create_block * 2u32.into(),
 				deposit: self.deposit,
 				properties: self.properties,
 				name: self.name,
 				description: self.description,
+				royalty_rate: Perbill::zero(),
+			}
+		}
+	}
+
+	/// `ClassData` of `Releases::V2_0_0`, without royalty.
+	#[derive(Encode, Decode)]
+	pub struct ClassDataV2<BlockNumber> {
+		#[codec(compact)]
+		pub deposit: Balance,
+		pub properties: Properties,
+		pub name: Vec<u8>,
+		pub description: Vec<u8>,
+		#[codec(compact)]
+		pub create_block: BlockNumber,
+	}
+
+	impl<BlockNumber> ClassDataV2<BlockNumber> {
+		fn upgraded(self) -> ClassDataV5<BlockNumber> {
+			ClassDataV5 {
+				deposit: self.deposit,
+				properties: self.properties,
+				name: self.name,
+				description: self.description,
+				create_block: self.create_block,
+				royalty_rate: Perbill::zero(),
 			}
 		}
 	}
 
+	/// `ClassData` of `Releases::V5_0_0`, without supply limits.
+	#[derive(Encode, Decode)]
+	pub struct ClassDataV5<BlockNumber> {
+		#[codec(compact)]
+		pub deposit: Balance,
+		pub properties: Properties,
+		pub name: Vec<u8>,
+		pub description: Vec<u8>,
+		#[codec(compact)]
+		pub create_block: BlockNumber,
+		pub royalty_rate: Perbill,
+	}
+
+	impl<BlockNumber> ClassDataV5<BlockNumber> {
+		fn upgraded(self) -> ClassData<BlockNumber> {
+			ClassData {
+				deposit: self.deposit,
+				properties: self.properties,
+				name: self.name,
+				description: self.description,
+				create_block: self.create_block,
+				royalty_rate: self.royalty_rate,
+				max_supply: None,
+				max_mint_per_account: None,
+			}
+		}
+	}
+
+	/// `orml_nft::TokenInfo` of `Releases::V4_0_0`, owned by a single account.
+	#[derive(Encode, Decode)]
+	pub struct TokenInfoV4<AccountId, Data> {
+		pub metadata: Vec<u8>,
+		pub owner: AccountId,
+		pub data: Data,
+	}
+
+	/// `OrderData` of `Releases::V4_0_0`, without quantity and side.
+	#[derive(Encode, Decode)]
+	pub struct OrderDataV4<T: Config> {
+		#[codec(compact)]
+		pub currency_id: CurrencyIdOf<T>,
+		#[codec(compact)]
+		pub price: Balance,
+		#[codec(compact)]
+		pub deposit: Balance,
+		#[codec(compact)]
+		pub deadline: BlockNumberOf<T>,
+		#[codec(compact)]
+		pub category_id: CategoryIdOf<T>,
+	}
+
 	impl OldTokenData {
 		fn upgraded<T>(self) -> TokenData<T> where T: AtLeast32BitUnsigned + Bounded + Copy + From<u32> {
 			let create_block: T = One::one();
@@ -171,29 +581,178 @@ pub mod migrations {
 	}
 
 	pub fn do_migrate<T: Config>() -> Weight {
+		// Classes are written in the layout of `Releases::V5_0_0`, `migrate_supply` upgrades them later.
 		type OldClass<T> = orml_nft::ClassInfo<TokenIdOf<T>, <T as frame_system::Config>::AccountId, OldClassData>;
-		type NewClass<T> = orml_nft::ClassInfo<TokenIdOf<T>, <T as frame_system::Config>::AccountId, ClassData<BlockNumberOf<T>>>;
-		orml_nft::Classes::<T>::translate::<OldClass<T>, _>(|_, p: OldClass<T>| {
+		type NewClass<T> = orml_nft::ClassInfo<TokenIdOf<T>, <T as frame_system::Config>::AccountId, ClassDataV5<BlockNumberOf<T>>>;
+		let module = <orml_nft::Classes<T> as StoragePrefixedMap<orml_nft::ClassInfoOf<T>>>::module_prefix();
+		let item = <orml_nft::Classes<T> as StoragePrefixedMap<orml_nft::ClassInfoOf<T>>>::storage_prefix();
+		for (key, p) in StorageIterator::<OldClass<T>>::new(module, item) {
 			let new_data: NewClass<T> = NewClass::<T> {
 				 metadata: p.metadata,
 				 total_issuance: p.total_issuance,
 				 owner: p.owner,
 				 data: p.data.upgraded::<BlockNumberOf<T>>(),
 			};
-			Some(new_data)
-		});
-		type OldToken<T> = orml_nft::TokenInfo<<T as frame_system::Config>::AccountId, OldTokenData>;
-		type NewToken<T> = orml_nft::TokenInfo<<T as frame_system::Config>::AccountId, TokenData<BlockNumberOf<T>>>;
-		orml_nft::Tokens::<T>::translate::<OldToken<T>, _>(|_, _, p: OldToken<T>| {
+			put_storage_value(module, item, &key, new_data);
+		}
+		// Tokens are written in the layout of `Releases::V4_0_0`, `migrate_sft` upgrades them later.
+		type OldToken<T> = TokenInfoV4<<T as frame_system::Config>::AccountId, OldTokenData>;
+		type NewToken<T> = TokenInfoV4<<T as frame_system::Config>::AccountId, TokenData<BlockNumberOf<T>>>;
+		let module = <orml_nft::Tokens<T> as StoragePrefixedMap<orml_nft::TokenInfoOf<T>>>::module_prefix();
+		let item = <orml_nft::Tokens<T> as StoragePrefixedMap<orml_nft::TokenInfoOf<T>>>::storage_prefix();
+		for (key, p) in StorageIterator::<OldToken<T>>::new(module, item) {
 			let new_data: NewToken<T> = NewToken::<T> {
 				metadata: p.metadata,
 				owner: p.owner,
 				data: p.data.upgraded::<BlockNumberOf<T>>(),
 			};
-			Some(new_data)
-		});
+			put_storage_value(module, item, &key, new_data);
+		}
 		T::BlockWeights::get().max_block
 	}
+
+	pub fn migrate_royalty<T: Config>() -> Weight {
+		type OldClass<T> = orml_nft::ClassInfo<TokenIdOf<T>, <T as frame_system::Config>::AccountId, ClassDataV2<BlockNumberOf<T>>>;
+		type NewClass<T> = orml_nft::ClassInfo<TokenIdOf<T>, <T as frame_system::Config>::AccountId, ClassDataV5<BlockNumberOf<T>>>;
+		let module = <orml_nft::Classes<T> as StoragePrefixedMap<orml_nft::ClassInfoOf<T>>>::module_prefix();
+		let item = <orml_nft::Classes<T> as StoragePrefixedMap<orml_nft::ClassInfoOf<T>>>::storage_prefix();
+		let mut count: Weight = 0;
+		for (key, p) in StorageIterator::<OldClass<T>>::new(module, item) {
+			count = count.saturating_add(1);
+			let new_data: NewClass<T> = NewClass::<T> {
+				metadata: p.metadata,
+				total_issuance: p.total_issuance,
+				owner: p.owner,
+				data: p.data.upgraded(),
+			};
+			put_storage_value(module, item, &key, new_data);
+		}
+		T::DbWeight::get().reads_writes(count, count)
+	}
+
+	/// Classes of `Releases::V5_0_0` have no supply limits, and count their issuance in `TokenId`.
+	pub fn migrate_supply<T: Config>() -> Weight {
+		type OldClass<T> = orml_nft::ClassInfo<TokenIdOf<T>, <T as frame_system::Config>::AccountId, ClassDataV5<BlockNumberOf<T>>>;
+		let mut count: Weight = 0;
+		orml_nft::Classes::<T>::translate::<OldClass<T>, _>(|_, p: OldClass<T>| {
+			count = count.saturating_add(1);
+			Some(orml_nft::ClassInfo {
+				metadata: p.metadata,
+				total_issuance: p.total_issuance.saturated_into(),
+				owner: p.owner,
+				data: p.data.upgraded(),
+			})
+		});
+		T::DbWeight::get().reads_writes(count, count)
+	}
+
+	pub fn migrate_order_expiry<T: Config>() -> Weight {
+		// Orders are in the layout of `Releases::V4_0_0`, the account follows the 16 bytes hash of the second key.
+		type OrderKey<T> = ((ClassIdOf<T>, TokenIdOf<T>), [u8; 16], <T as frame_system::Config>::AccountId);
+		let module = <Orders<T> as StoragePrefixedMap<OrderData<T>>>::module_prefix();
+		let item = <Orders<T> as StoragePrefixedMap<OrderData<T>>>::storage_prefix();
+		let mut count: Weight = 0;
+		let mut cursor = <frame_system::Pallet<T>>::block_number();
+		for ((token, _, who), order) in StorageKeyIterator::<OrderKey<T>, OrderDataV4<T>, Blake2_128Concat>::new(module, item) {
+			count = count.saturating_add(1);
+			cursor = cursor.min(order.deadline);
+			OrderExpiry::<T>::insert(order.deadline, ExpiringOrder::Order(token, who), ());
+		}
+		ExpiryCursor::<T>::put(cursor);
+		T::DbWeight::get().reads_writes(count, count.saturating_add(1))
+	}
+
+	/// Every token of `Releases::V4_0_0` becomes a token of quantity one held by its owner.
+	pub fn migrate_sft<T: Config>() -> Weight {
+		type OldToken<T> = TokenInfoV4<<T as frame_system::Config>::AccountId, TokenData<BlockNumberOf<T>>>;
+		let mut count: Weight = 0;
+		// The old `()` index is rebuilt from the owner stored in each token.
+		orml_nft::TokensByOwner::<T>::remove_all();
+		orml_nft::Tokens::<T>::translate::<OldToken<T>, _>(|class_id, token_id, p: OldToken<T>| {
+			count = count.saturating_add(1);
+			orml_nft::TokensByOwner::<T>::insert(&p.owner, (class_id, token_id), QuantityOf::<T>::one());
+			orml_nft::OwnersByToken::<T>::insert((class_id, token_id), p.owner, ());
+			Some(orml_nft::TokenInfo {
+				metadata: p.metadata,
+				quantity: One::one(),
+				data: p.data,
+			})
+		});
+		Orders::<T>::translate::<OrderDataV4<T>, _>(|token, who, p: OrderDataV4<T>| {
+			count = count.saturating_add(1);
+			Some(OrderData {
+				currency_id: p.currency_id,
+				price: p.price,
+				deposit: p.deposit,
+				deadline: p.deadline,
+				category_id: p.category_id,
+				quantity: One::one(),
+				by_token_owner: orml_nft::Module::<T>::is_owner(&who, token),
+			})
+		});
+		T::DbWeight::get().reads_writes(count, count.saturating_mul(3))
+	}
+
+	/// Orders of `Releases::V6_0_0` are only indexed by token and deadline, and `nft_count` of
+	/// categories counted dutch and bundle orders as well. It is recounted from the new index.
+	pub fn migrate_order_indexes<T: Config>() -> Weight {
+		let mut count: Weight = 0;
+		for (token, who, order) in Orders::<T>::iter() {
+			count = count.saturating_add(1);
+			OrdersByAccount::<T>::insert(&who, token, ());
+			OrdersByCategory::<T>::insert(order.category_id, (token, who), ());
+		}
+		let mut categories: Weight = 0;
+		Categories::<T>::translate::<CategoryData, _>(|category_id, mut category| {
+			categories = categories.saturating_add(1);
+			category.nft_count = OrdersByCategory::<T>::iter_prefix(category_id).count() as Balance;
+			Some(category)
+		});
+		T::DbWeight::get().reads_writes(count.saturating_mul(2).saturating_add(categories), count.saturating_mul(2).saturating_add(categories))
+	}
+
+	/// The expiry index of `Releases::V7_0_0` only held orders of `Orders`, it is rebuilt with
+	/// dutch orders, bundle orders and collection offers as well.
+	pub fn migrate_expiry_index<T: Config>() -> Weight {
+		<OrderExpiry<T> as StoragePrefixedMap<()>>::remove_all();
+		let mut count: Weight = 0;
+		let mut cursor = ExpiryCursor::<T>::get();
+		let mut index = |deadline: BlockNumberOf<T>, order: ExpiringOrderOf<T>| {
+			count = count.saturating_add(1);
+			cursor = cursor.min(deadline);
+			OrderExpiry::<T>::insert(deadline, order, ());
+		};
+		for (token, who, order) in Orders::<T>::iter() {
+			index(order.deadline, ExpiringOrder::Order(token, who));
+		}
+		for (token, order) in DutchOrders::<T>::iter() {
+			index(order.end_block, ExpiringOrder::Dutch(token));
+		}
+		for (order_id, order) in BundleOrders::<T>::iter() {
+			index(order.deadline, ExpiringOrder::Bundle(order_id));
+		}
+		for (class_id, offer_id, offer) in Offers::<T>::iter() {
+			index(offer.deadline, ExpiringOrder::Offer(class_id, offer_id));
+		}
+		ExpiryCursor::<T>::put(cursor);
+		T::DbWeight::get().reads_writes(count.saturating_add(1), count.saturating_add(1))
+	}
+
+	/// `MintedByClass` is new in `Releases::V9_0_0`, it starts from the current issuance of classes.
+	pub fn migrate_minted_by_class<T: Config>() -> Weight {
+		let mut count: Weight = 0;
+		for (class_id, class_info) in orml_nft::Classes::<T>::iter() {
+			count = count.saturating_add(1);
+			MintedByClass::<T>::insert(class_id, class_info.total_issuance);
+		}
+		T::DbWeight::get().reads_writes(count, count)
+	}
+
+	/// Rentals of `Releases::V9_0_0` were removed exactly at their end, so the cursor starts from the current block.
+	pub fn migrate_rental_expiry_cursor<T: Config>() -> Weight {
+		RentalExpiryCursor::<T>::put(<frame_system::Pallet<T>>::block_number());
+		T::DbWeight::get().reads_writes(1, 1)
+	}
 }
 
 #[frame_support::pallet]
@@ -203,7 +762,7 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config +
-		orml_nft::Config<ClassData = ClassData<BlockNumberOf<Self>>, TokenData = TokenData<BlockNumberOf<Self>>> +
+		orml_nft::Config<ClassData = ClassData<BlockNumberOf<Self>>, TokenData = TokenData<BlockNumberOf<Self>>, Quantity = Quantity> +
 		pallet_proxy::Config +
 		nftmart_config::Config
 	{
@@ -226,41 +785,103 @@ pub mod module {
 		type ModuleId: Get<ModuleId>;
 
 		/// MultiCurrency type for trading
-		type MultiCurrency: MultiReservableCurrency<Self::AccountId, Balance = Balance>;
+		type MultiCurrency: MultiReservableCurrency<Self::AccountId, Balance = Balance, CurrencyId = <Self as nftmart_config::Config>::CurrencyId>;
 
 		/// The currency mechanism.
 		type Currency: ReservableCurrency<Self::AccountId>;
 
 		/// The Category ID type
 		type CategoryId: Parameter + Member + AtLeast32BitUnsigned + Default + Copy + MaybeSerializeDeserialize + Bounded + FullCodec;
-	}
 
-	#[pallet::error]
-	pub enum Error<T> {
-		/// ClassId not found
-		ClassIdNotFound,
-		/// TokenId not found
-		TokenIdNotFound,
-		/// Order not found
-		OrderNotFound,
-		/// Category not found
-		CategoryNotFound,
-		/// The operator is not the owner of the token and has no permission
-		NoPermission,
-		/// Quantity is invalid. need >= 1
-		InvalidQuantity,
-		/// Invalid deadline
-		InvalidDeadline,
-		/// Invalid deposit
-		InvalidDeposit,
-		/// Property of class don't support transfer
-		NonTransferable,
-		/// Property of class don't support burn
-		NonBurnable,
-		/// Property of class support burn
-		Burnable,
-		/// Can not destroy class
-		/// Total issuance is not 0
+		/// The account receiving platform fees, e.g. the treasury pot.
+		type PlatformFeeAccount: Get<Self::AccountId>;
+
+		/// The maximum royalty rate of a class.
+		#[pallet::constant]
+		type MaxRoyaltyRate: Get<Perbill>;
+
+		/// The maximum number of expired orders removed in a block.
+		#[pallet::constant]
+		type MaxExpiredOrdersPerBlock: Get<u32>;
+
+		/// The maximum number of expired rentals removed in a block.
+		#[pallet::constant]
+		type MaxExpiredRentalsPerBlock: Get<u32>;
+
+		/// The maximum number of auctions ending in a block, they are all settled in that block.
+		#[pallet::constant]
+		type MaxAuctionsEndingPerBlock: Get<u32>;
+
+		/// The maximum number of tokens in a bundle order.
+		#[pallet::constant]
+		type MaxBundleSize: Get<u32>;
+
+		/// The maximum number of orders on a token, they are all scanned to match a new order.
+		#[pallet::constant]
+		type MaxOrdersPerToken: Get<u32>;
+
+		/// The maximum length of an attribute key.
+		#[pallet::constant]
+		type MaxAttributeKeyLength: Get<u32>;
+
+		/// The maximum encoded length of an attribute value.
+		#[pallet::constant]
+		type MaxAttributeValueLength: Get<u32>;
+
+		/// The maximum number of currencies a class can restrict its trades to.
+		#[pallet::constant]
+		type MaxClassCurrencies: Get<u32>;
+
+		/// The maximum number of nodes in a Merkle proof of a drop allowlist.
+		#[pallet::constant]
+		type MaxProofLength: Get<u32>;
+
+		/// The maximum length of the metadata of a drop.
+		#[pallet::constant]
+		type MaxDropMetadataLength: Get<u32>;
+
+		/// Maps a class admin role to the proxy type granted to the admin.
+		type ClassAdminProxy: Convert<ClassAdminRole, <Self as pallet_proxy::Config>::ProxyType>;
+
+		/// The signature of a mint voucher.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		/// The public key signing a mint voucher.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// Creates the currencies of fractionalized tokens' shares.
+		type CurrencyFactory: CreateCurrency<Self::AccountId, CurrencyId = CurrencyIdOf<Self>>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// ClassId not found
+		ClassIdNotFound,
+		/// TokenId not found
+		TokenIdNotFound,
+		/// Order not found
+		OrderNotFound,
+		/// Category not found
+		CategoryNotFound,
+		/// The operator is not the owner of the token and has no permission
+		NoPermission,
+		/// Quantity is invalid. need >= 1
+		InvalidQuantity,
+		/// Invalid deadline
+		InvalidDeadline,
+		/// Invalid deposit
+		InvalidDeposit,
+		/// Property of class don't support transfer
+		NonTransferable,
+		/// Property of class don't support burn
+		NonBurnable,
+		/// Property of class support burn
+		Burnable,
+		/// Can not destroy class
+		/// Total issuance is not 0
 		CannotDestroyClass,
 		/// No available category ID
 		NoAvailableCategoryId,
@@ -282,6 +903,102 @@ pub mod module {
 		DescriptionTooLong,
 		/// account not in whitelist
 		AccountNotInWhitelist,
+		/// No available global ID
+		NoAvailableId,
+		/// Auction not found
+		AuctionNotFound,
+		/// Auction not started yet
+		AuctionNotStarted,
+		/// Auction already ended
+		AuctionExpired,
+		/// Invalid auction start or end block
+		InvalidAuctionPeriod,
+		/// The bid is lower than the reserve price or the minimum raise.
+		InvalidBidPrice,
+		/// The bid has been rejected.
+		BidNotAccepted,
+		/// Cannot cancel an auction which has bids.
+		AuctionHasBids,
+		/// `MaxAuctionsEndingPerBlock` auctions already end at this block
+		TooManyEndingAuctions,
+		/// The floor price is higher than the start price.
+		InvalidDutchPrice,
+		/// Offer not found
+		OfferNotFound,
+		/// A bundle needs at least two distinct tokens and no more than `MaxBundleSize`.
+		InvalidBundle,
+		/// Metadata of the class has been frozen
+		MetadataFrozen,
+		/// A class must keep at least one admin with full permissions
+		CannotRemoveLastAdmin,
+		/// Minting would exceed the max supply of the class
+		ExceedMaxSupply,
+		/// Minting would exceed the max quantity minted to an account
+		ExceedMaxMintPerAccount,
+		/// Drop not found
+		DropNotFound,
+		/// The drop must end after it starts
+		InvalidDropPeriod,
+		/// The drop is not open at this block
+		DropNotActive,
+		/// The account is not in the allowlist of the drop
+		NotInAllowlist,
+		/// The Merkle proof is longer than `MaxProofLength`
+		ProofTooLong,
+		/// The metadata of a drop is longer than `MaxDropMetadataLength`
+		DropMetadataTooLong,
+		/// Minting would exceed the max quantity of the drop per wallet
+		ExceedMaxPerWallet,
+		/// The signature or the signer of the voucher is invalid
+		InvalidVoucher,
+		/// The voucher has expired
+		VoucherExpired,
+		/// The voucher has been redeemed
+		VoucherRedeemed,
+		/// The metadata does not match the hash in the voucher
+		MetadataMismatch,
+		/// Rental listing not found
+		RentalNotFound,
+		/// The token is being rented
+		RentalActive,
+		/// The rental duration is zero or longer than the listing allows
+		InvalidRentalDuration,
+		/// Vault not found
+		VaultNotFound,
+		/// The fractionalized token has been bought out
+		BoughtOut,
+		/// The fractionalized token has not been bought out
+		NotBoughtOut,
+		/// The account does not hold enough shares
+		InsufficientShares,
+		/// The token was bought out of a vault whose proceeds are not all claimed
+		VaultExists,
+		/// The token is not nested
+		NotNested,
+		/// Property of class don't support revocation
+		NonRevocable,
+		/// A revocable class must not be transferable
+		InvalidProperties,
+		/// The royalty rate is above `MaxRoyaltyRate`
+		RoyaltyRateTooHigh,
+		/// The token already has `MaxOrdersPerToken` orders
+		TooManyOrders,
+		/// The platform fee rate plus `MaxRoyaltyRate` is above 100%
+		PlatformFeeRateTooHigh,
+		/// The attribute key is longer than `MaxAttributeKeyLength`
+		AttributeKeyTooLong,
+		/// The attribute value is longer than `MaxAttributeValueLength`
+		AttributeValueTooLong,
+		/// Attribute not found
+		AttributeNotFound,
+		/// The currency is not accepted for trading, or not for tokens of this class
+		CurrencyNotAccepted,
+		/// The price is zero or below the minimum price of the currency
+		PriceBelowFloor,
+		/// Too many currencies for a class
+		TooManyCurrencies,
+		/// Only the holder of a token can escrow it in a sell order
+		CannotEscrowBid,
 	}
 
 	#[pallet::event]
@@ -289,12 +1006,16 @@ pub mod module {
 	pub enum Event<T: Config> {
 		/// Created NFT class. \[owner, class_id\]
 		CreatedClass(T::AccountId, ClassIdOf<T>),
-		/// Minted NFT token. \[from, to, class_id, quantity\]
-		MintedToken(T::AccountId, T::AccountId, ClassIdOf<T>, u32),
-		/// Transferred NFT token. \[from, to, class_id, token_id\]
-		TransferredToken(T::AccountId, T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
-		/// Burned NFT token. \[owner, class_id, token_id\]
-		BurnedToken(T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
+		/// Minted NFT token. \[from, to, class_id, token_id, quantity\]
+		MintedToken(T::AccountId, T::AccountId, ClassIdOf<T>, TokenIdOf<T>, QuantityOf<T>),
+		/// Transferred NFT token. \[from, to, class_id, token_id, quantity\]
+		TransferredToken(T::AccountId, T::AccountId, ClassIdOf<T>, TokenIdOf<T>, QuantityOf<T>),
+		/// Burned NFT token. \[owner, class_id, token_id, quantity\]
+		BurnedToken(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, QuantityOf<T>),
+		/// Approved an account to transfer a token. \[owner, class_id, token_id, spender\]
+		Approval(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, Option<T::AccountId>),
+		/// Approved or disapproved an operator of all tokens. \[owner, operator, approved\]
+		ApprovalForAll(T::AccountId, T::AccountId, bool),
 		/// Destroyed NFT class. \[owner, class_id, dest\]
 		DestroyedClass(T::AccountId, ClassIdOf<T>, T::AccountId),
 		/// Created NFT common category. \[category_id\]
@@ -311,6 +1032,98 @@ pub mod module {
 		UpdatedOrderPrice(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, Balance),
 		/// OrderMinDeposit updated \[old, new\]
 		UpdatedMinOrderDeposit(Balance, Balance),
+		/// Created an auction. \[auction_id, class_id, token_id, owner\]
+		CreatedAuction(GlobalId, ClassIdOf<T>, TokenIdOf<T>, T::AccountId),
+		/// A bid is placed. \[auction_id, bidder, bid_amount\]
+		Bid(GlobalId, T::AccountId, Balance),
+		/// An auction has been settled. \[auction_id, winner, price\]
+		AuctionSettled(GlobalId, T::AccountId, Balance),
+		/// An auction ended without a winner, the NFT was returned. \[auction_id, owner\]
+		AuctionEndedWithoutBid(GlobalId, T::AccountId),
+		/// An auction has been cancelled by its owner. \[auction_id\]
+		CancelledAuction(GlobalId),
+		/// Created a dutch order. \[class_id, token_id, order_owner\]
+		CreatedDutchOrder(ClassIdOf<T>, TokenIdOf<T>, T::AccountId),
+		/// Removed a dutch order. \[class_id, token_id, order_owner, unreserved\]
+		RemovedDutchOrder(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, Balance),
+		/// A dutch order had been taken. \[class_id, token_id, order_owner, buyer, price\]
+		TakenDutchOrder(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, T::AccountId, Balance),
+		/// Royalty paid to the class beneficiary. \[class_id, beneficiary, currency_id, amount\]
+		RoyaltyPaid(ClassIdOf<T>, T::AccountId, CurrencyIdOf<T>, Balance),
+		/// Royalty beneficiary updated. \[class_id, beneficiary\]
+		UpdatedRoyaltyBeneficiary(ClassIdOf<T>, T::AccountId),
+		/// Platform fee paid to the fee account. \[class_id, currency_id, amount\]
+		PlatformFeePaid(ClassIdOf<T>, CurrencyIdOf<T>, Balance),
+		/// PlatformFeeRate updated \[old, new\]
+		UpdatedPlatformFeeRate(Perbill, Perbill),
+		/// Created a collection offer. \[class_id, offer_id, offer_owner\]
+		CreatedOffer(ClassIdOf<T>, GlobalId, T::AccountId),
+		/// A token was sold to a collection offer. \[class_id, offer_id, token_id, seller, remaining_quantity\]
+		FilledOffer(ClassIdOf<T>, GlobalId, TokenIdOf<T>, T::AccountId, QuantityOf<T>),
+		/// Removed a collection offer. \[class_id, offer_id, offer_owner, unreserved\]
+		RemovedOffer(ClassIdOf<T>, GlobalId, T::AccountId, Balance),
+		/// Created a bundle order. \[order_id, order_owner\]
+		CreatedBundleOrder(GlobalId, T::AccountId),
+		/// Removed a bundle order. \[order_id, order_owner, unreserved\]
+		RemovedBundleOrder(GlobalId, T::AccountId, Balance),
+		/// A bundle order had been taken. \[order_id, order_owner, buyer\]
+		TakenBundleOrder(GlobalId, T::AccountId, T::AccountId),
+		/// Updated metadata, name and description of a class. \[class_id\]
+		UpdatedClass(ClassIdOf<T>),
+		/// Updated metadata of a token. \[class_id, token_id\]
+		UpdatedToken(ClassIdOf<T>, TokenIdOf<T>),
+		/// Metadata of a class and its tokens has been frozen. \[class_id\]
+		FrozenMetadata(ClassIdOf<T>),
+		/// Added an admin to a class. \[class_id, admin, role\]
+		AddedClassAdmin(ClassIdOf<T>, T::AccountId, ClassAdminRole),
+		/// Removed an admin from a class. \[class_id, admin, role\]
+		RemovedClassAdmin(ClassIdOf<T>, T::AccountId, ClassAdminRole),
+		/// Configured the drop of a class. \[class_id\]
+		SetDrop(ClassIdOf<T>),
+		/// Removed the drop of a class. \[class_id\]
+		RemovedDrop(ClassIdOf<T>),
+		/// Redeemed a mint voucher. \[class_id, nonce, signer, buyer\]
+		RedeemedVoucher(ClassIdOf<T>, u64, T::AccountId, T::AccountId),
+		/// Listed a token for rent. \[class_id, token_id, lender\]
+		ListedRental(ClassIdOf<T>, TokenIdOf<T>, T::AccountId),
+		/// Removed a rental listing. \[class_id, token_id\]
+		UnlistedRental(ClassIdOf<T>, TokenIdOf<T>),
+		/// Rented a token. \[class_id, token_id, user, end\]
+		Rented(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, BlockNumberOf<T>),
+		/// A rental has expired. \[class_id, token_id, user\]
+		RentalExpired(ClassIdOf<T>, TokenIdOf<T>, T::AccountId),
+		/// Fractionalized a token into shares. \[class_id, token_id, share_currency_id, shares\]
+		Fractionalized(ClassIdOf<T>, TokenIdOf<T>, CurrencyIdOf<T>, Balance),
+		/// Redeemed a fractionalized token with all its shares. \[class_id, token_id, who\]
+		RedeemedFractions(ClassIdOf<T>, TokenIdOf<T>, T::AccountId),
+		/// Bought out a fractionalized token. \[class_id, token_id, buyer, price\]
+		BoughtOut(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, Balance),
+		/// Exchanged shares for buyout proceeds. \[class_id, token_id, who, shares, proceeds\]
+		ClaimedProceeds(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, Balance, Balance),
+		/// Nested a token into another one. \[class_id, token_id, parent_class_id, parent_token_id\]
+		Nested(ClassIdOf<T>, TokenIdOf<T>, ClassIdOf<T>, TokenIdOf<T>),
+		/// Took a nested token out of its parent. \[class_id, token_id, parent_class_id, parent_token_id\]
+		Unnested(ClassIdOf<T>, TokenIdOf<T>, ClassIdOf<T>, TokenIdOf<T>),
+		/// Revoked a token by the class owner. \[class_id, token_id, owner, quantity\]
+		RevokedToken(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, QuantityOf<T>),
+		/// Reassigned a token by the class owner. \[class_id, token_id, from, to, quantity\]
+		ReassignedToken(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, T::AccountId, QuantityOf<T>),
+		/// Set an attribute of a class. \[class_id, key\]
+		SetClassAttribute(ClassIdOf<T>, Vec<u8>),
+		/// Cleared an attribute of a class. \[class_id, key\]
+		ClearedClassAttribute(ClassIdOf<T>, Vec<u8>),
+		/// Set an attribute of a token. \[class_id, token_id, key\]
+		SetTokenAttribute(ClassIdOf<T>, TokenIdOf<T>, Vec<u8>),
+		/// Cleared an attribute of a token. \[class_id, token_id, key\]
+		ClearedTokenAttribute(ClassIdOf<T>, TokenIdOf<T>, Vec<u8>),
+		/// Updated the currencies accepted for tokens of a class. \[class_id\]
+		UpdatedClassCurrencies(ClassIdOf<T>),
+		/// Moved a listed token into escrow. \[class_id, token_id, order_owner, quantity\]
+		EscrowedToken(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, QuantityOf<T>),
+		/// Returned a token in escrow to the owner of a cancelled or expired order. \[class_id, token_id, order_owner, quantity\]
+		ReleasedToken(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, QuantityOf<T>),
+		/// Delivered a token in escrow to the buyer of a taken order. \[class_id, token_id, seller, buyer, quantity\]
+		SoldEscrowedToken(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, T::AccountId, QuantityOf<T>),
 	}
 
 	#[pallet::pallet]
@@ -319,12 +1132,61 @@ pub mod module {
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
 		fn on_runtime_upgrade() -> Weight {
-			if StorageVersion::<T>::get() == Releases::V1_0_0 {
-				StorageVersion::<T>::put(Releases::V2_0_0);
-				migrations::do_migrate::<T>()
-			} else {
-				0
+			let mut weight: Weight = 0;
+			// Both migrations upgrade `ClassData` to the layout of `Releases::V5_0_0`.
+			match StorageVersion::<T>::get() {
+				Releases::V1_0_0 => {
+					StorageVersion::<T>::put(Releases::V3_0_0);
+					weight = weight.saturating_add(migrations::do_migrate::<T>());
+				},
+				Releases::V2_0_0 => {
+					StorageVersion::<T>::put(Releases::V3_0_0);
+					weight = weight.saturating_add(migrations::migrate_royalty::<T>());
+				},
+				_ => {},
+			}
+			if StorageVersion::<T>::get() == Releases::V3_0_0 {
+				StorageVersion::<T>::put(Releases::V4_0_0);
+				weight = weight.saturating_add(migrations::migrate_order_expiry::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V4_0_0 {
+				StorageVersion::<T>::put(Releases::V5_0_0);
+				weight = weight.saturating_add(migrations::migrate_sft::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V5_0_0 {
+				StorageVersion::<T>::put(Releases::V6_0_0);
+				weight = weight.saturating_add(migrations::migrate_supply::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V6_0_0 {
+				StorageVersion::<T>::put(Releases::V7_0_0);
+				weight = weight.saturating_add(migrations::migrate_order_indexes::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V7_0_0 {
+				StorageVersion::<T>::put(Releases::V8_0_0);
+				weight = weight.saturating_add(migrations::migrate_expiry_index::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V8_0_0 {
+				StorageVersion::<T>::put(Releases::V9_0_0);
+				weight = weight.saturating_add(migrations::migrate_minted_by_class::<T>());
 			}
+			if StorageVersion::<T>::get() == Releases::V9_0_0 {
+				StorageVersion::<T>::put(Releases::V10_0_0);
+				weight = weight.saturating_add(migrations::migrate_rental_expiry_cursor::<T>());
+			}
+			weight
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut count: Weight = 0;
+			for (auction_id, _) in AuctionEndTime::<T>::drain_prefix(&now) {
+				count = count.saturating_add(1);
+				if let Some(auction) = Auctions::<T>::take(auction_id) {
+					Self::on_auction_ended(auction_id, auction.bid);
+				}
+			}
+			T::DbWeight::get().reads_writes(count.saturating_mul(6).saturating_add(1), count.saturating_mul(8))
+				.saturating_add(Self::remove_expired_orders(now))
+				.saturating_add(Self::remove_expired_rentals(now))
 		}
 
 		fn integrity_test () {}
@@ -333,6 +1195,7 @@ pub mod module {
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		min_order_deposit: Balance,
+		platform_fee_rate: Perbill,
 		_phantom: PhantomData<T>,
 	}
 
@@ -341,6 +1204,7 @@ pub mod module {
 		fn default() -> Self {
 			Self {
 				min_order_deposit: ACCURACY,
+				platform_fee_rate: Perbill::zero(),
 				_phantom: Default::default(),
 			}
 		}
@@ -351,6 +1215,7 @@ pub mod module {
 		fn build(&self) {
 			<StorageVersion<T>>::put(Releases::default());
 			MinOrderDeposit::<T>::put(self.min_order_deposit);
+			PlatformFeeRate::<T>::put(self.platform_fee_rate);
 		}
 	}
 
@@ -373,11 +1238,152 @@ pub mod module {
 	#[pallet::getter(fn orders)]
 	pub type Orders<T: Config> = StorageDoubleMap<_, Blake2_128Concat, (ClassIdOf<T>, TokenIdOf<T>), Blake2_128Concat, T::AccountId, OrderData<T>>;
 
+	/// An index mapping from deadline to orders, dutch orders, bundle orders and collection offers.
+	#[pallet::storage]
+	#[pallet::getter(fn order_expiry)]
+	pub type OrderExpiry<T: Config> = StorageDoubleMap<_, Twox64Concat, BlockNumberOf<T>, Blake2_128Concat, ExpiringOrderOf<T>, (), OptionQuery>;
+
+	/// An index mapping from account to the tokens it has orders on.
+	#[pallet::storage]
+	#[pallet::getter(fn orders_by_account_index)]
+	pub type OrdersByAccount<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, (ClassIdOf<T>, TokenIdOf<T>), (), OptionQuery>;
+
+	/// An index mapping from category to orders.
+	#[pallet::storage]
+	#[pallet::getter(fn orders_by_category)]
+	pub type OrdersByCategory<T: Config> = StorageDoubleMap<_, Twox64Concat, CategoryIdOf<T>, Blake2_128Concat, ((ClassIdOf<T>, TokenIdOf<T>), T::AccountId), (), OptionQuery>;
+
+	/// Sell orders whose tokens are held by the escrow account.
+	#[pallet::storage]
+	#[pallet::getter(fn escrowed_orders)]
+	pub type EscrowedOrders<T: Config> = StorageDoubleMap<_, Blake2_128Concat, (ClassIdOf<T>, TokenIdOf<T>), Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// Orders of `OrderExpiry` whose deadline is before `ExpiryCursor` have been removed.
+	#[pallet::storage]
+	#[pallet::getter(fn expiry_cursor)]
+	pub type ExpiryCursor<T: Config> = StorageValue<_, BlockNumberOf<T>, ValueQuery>;
+
 	/// Order deposit config
 	#[pallet::storage]
 	#[pallet::getter(fn min_order_deposit)]
 	pub type MinOrderDeposit<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	/// Descending price orders, indexed by token.
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_orders)]
+	pub type DutchOrders<T: Config> = StorageMap<_, Blake2_128Concat, (ClassIdOf<T>, TokenIdOf<T>), DutchOrderData<T>>;
+
+	/// The rate of platform fee deducted from every trade.
+	#[pallet::storage]
+	#[pallet::getter(fn platform_fee_rate)]
+	pub type PlatformFeeRate<T: Config> = StorageValue<_, Perbill, ValueQuery>;
+
+	/// The account receiving royalties of a class. Defaults to the class owner.
+	#[pallet::storage]
+	#[pallet::getter(fn royalty_beneficiaries)]
+	pub type RoyaltyBeneficiaries<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, T::AccountId>;
+
+	/// Collection offers, buying any token of a class.
+	#[pallet::storage]
+	#[pallet::getter(fn offers)]
+	pub type Offers<T: Config> = StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, GlobalId, OfferData<T>>;
+
+	/// Orders selling several tokens together.
+	#[pallet::storage]
+	#[pallet::getter(fn bundle_orders)]
+	pub type BundleOrders<T: Config> = StorageMap<_, Twox64Concat, GlobalId, BundleOrderData<T>>;
+
+	/// An index mapping from token to the bundle order containing it.
+	#[pallet::storage]
+	#[pallet::getter(fn token_bundles)]
+	pub type TokenBundles<T: Config> = StorageMap<_, Blake2_128Concat, (ClassIdOf<T>, TokenIdOf<T>), GlobalId>;
+
+	/// Next available global ID.
+	#[pallet::storage]
+	#[pallet::getter(fn next_id)]
+	pub type NextId<T: Config> = StorageValue<_, GlobalId, ValueQuery>;
+
+	/// The quantity of tokens ever minted of a class, burning does not decrease it.
+	#[pallet::storage]
+	#[pallet::getter(fn minted_by_class)]
+	pub type MintedByClass<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, QuantityOf<T>, ValueQuery>;
+
+	/// The quantity of tokens of a class minted to an account.
+	#[pallet::storage]
+	#[pallet::getter(fn minted_by_account)]
+	pub type MintedByAccount<T: Config> = StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Blake2_128Concat, T::AccountId, QuantityOf<T>, ValueQuery>;
+
+	/// Drops of classes, open for minting by anyone, with the deposit reserved from the class owner.
+	#[pallet::storage]
+	#[pallet::getter(fn drops)]
+	pub type Drops<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, (DropData<T>, Balance)>;
+
+	/// The quantity of tokens minted by an account through the drop of a class.
+	#[pallet::storage]
+	#[pallet::getter(fn drop_minted)]
+	pub type DropMinted<T: Config> = StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Blake2_128Concat, T::AccountId, QuantityOf<T>, ValueQuery>;
+
+	/// Nonces of redeemed mint vouchers of a class.
+	#[pallet::storage]
+	#[pallet::getter(fn redeemed_vouchers)]
+	pub type RedeemedVouchers<T: Config> = StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, u64, (), OptionQuery>;
+
+	/// Tokens listed for rent.
+	#[pallet::storage]
+	#[pallet::getter(fn rental_listings)]
+	pub type RentalListings<T: Config> = StorageMap<_, Blake2_128Concat, (ClassIdOf<T>, TokenIdOf<T>), RentalListing<T>>;
+
+	/// Usage rights of rented tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn rentals)]
+	pub type Rentals<T: Config> = StorageMap<_, Blake2_128Concat, (ClassIdOf<T>, TokenIdOf<T>), RentalData<T>>;
+
+	/// An index mapping from end block to rentals.
+	#[pallet::storage]
+	#[pallet::getter(fn rental_expiry)]
+	pub type RentalExpiry<T: Config> = StorageDoubleMap<_, Twox64Concat, BlockNumberOf<T>, Blake2_128Concat, (ClassIdOf<T>, TokenIdOf<T>), (), OptionQuery>;
+
+	/// Rentals of `RentalExpiry` ending before `RentalExpiryCursor` have been removed.
+	#[pallet::storage]
+	#[pallet::getter(fn rental_expiry_cursor)]
+	pub type RentalExpiryCursor<T: Config> = StorageValue<_, BlockNumberOf<T>, ValueQuery>;
+
+	/// Vaults of fractionalized tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn vaults)]
+	pub type Vaults<T: Config> = StorageMap<_, Blake2_128Concat, (ClassIdOf<T>, TokenIdOf<T>), VaultData<T>>;
+
+	/// Attributes of a class and the deposit reserved for each of them.
+	#[pallet::storage]
+	#[pallet::getter(fn class_attributes)]
+	pub type ClassAttributes<T: Config> = StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Blake2_128Concat, Vec<u8>, (AttributeValue, Balance)>;
+
+	/// Attributes of a token and the deposit reserved for each of them.
+	#[pallet::storage]
+	#[pallet::getter(fn token_attributes)]
+	pub type TokenAttributes<T: Config> = StorageDoubleMap<_, Blake2_128Concat, (ClassIdOf<T>, TokenIdOf<T>), Blake2_128Concat, Vec<u8>, (AttributeValue, Balance)>;
+
+	/// The only currencies accepted for tokens of a class and the deposit reserved for them,
+	/// any currency accepted by `nftmart_config` if not set.
+	#[pallet::storage]
+	#[pallet::getter(fn class_currencies)]
+	pub type ClassCurrencies<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, (Vec<CurrencyIdOf<T>>, Balance)>;
+
+	/// Stores on-going and future auctions. Closed auction are removed.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> = StorageMap<_, Twox64Concat, GlobalId, AuctionInfo<T::AccountId, Balance, BlockNumberOf<T>>>;
+
+	/// The NFT and trading rules of an auction.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_items)]
+	pub type AuctionItems<T: Config> = StorageMap<_, Twox64Concat, GlobalId, AuctionItem<T>>;
+
+	/// Index auctions by end time.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_end_time)]
+	pub type AuctionEndTime<T: Config> = StorageDoubleMap<_, Twox64Concat, BlockNumberOf<T>, Twox64Concat, GlobalId, (), OptionQuery>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Take an NFT order.
@@ -385,7 +1391,7 @@ pub mod module {
 		/// - `class_id`: class id
 		/// - `token_id`: token id
 		/// - `price`: The max/min price to take an order. Usually it is set to the price of the target order.
-		#[pallet::weight(100_000)]
+		#[pallet::weight(T::WeightInfo::take_order())]
 		#[transactional]
 		pub fn take_order(
 			origin: OriginFor<T>,
@@ -397,55 +1403,41 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 			ensure!(order_owner != who, Error::<T>::TakeOwnOrder);
 
-			let order: OrderData<T> = {
-				let order = Self::orders((class_id, token_id), &order_owner);
-				ensure!(order.is_some(), Error::<T>::OrderNotFound);
-				order.unwrap()
-			};
-
+			let order: OrderData<T> = Self::orders((class_id, token_id), &order_owner).ok_or(Error::<T>::OrderNotFound)?;
 			ensure!(<frame_system::Pallet<T>>::block_number() <= order.deadline, Error::<T>::OrderExpired);
 
-			let token_owner = orml_nft::Module::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?.owner;
-			match (order_owner == token_owner, token_owner == who) {
-				(true, false) => {
-					ensure!(price >= order.price, Error::<T>::CanNotAfford);
-					// `who` will take the order submitting by `order_owner`/`token_owner`
-					Self::delete_order(class_id, token_id, &order_owner, &token_owner)?;
-					Self::try_delete_order(class_id, token_id, &who, &token_owner);
-					// `order_owner` transfers this NFT to `who`
-					Self::do_transfer(&order_owner, &who, class_id, token_id)?;
-					T::MultiCurrency::transfer(order.currency_id, &who, &order_owner, order.price)?;
-					// TODO: T::MultiCurrency::transfer(order.currency_id, &order_owner, some_account,platform-fee)?;
-					Self::deposit_event(Event::TakenOrder(class_id, token_id, order_owner));
-				},
-				(false, true) => {
-					ensure!(price <= order.price, Error::<T>::PriceTooLow);
-					// `who`/`token_owner` will accept the order submitting by `order_owner`
-					Self::delete_order(class_id, token_id, &order_owner, &token_owner)?;
-					Self::try_delete_order(class_id, token_id, &who, &token_owner);
-					// `order_owner` transfers this NFT to `who`
-					Self::do_transfer(&who, &order_owner, class_id, token_id)?;
-					T::MultiCurrency::transfer(order.currency_id, &order_owner, &who, order.price)?;
-					// TODO: T::MultiCurrency::transfer(order.currency_id, &who, some_account,platform-fee)?;
-					Self::deposit_event(Event::TakenOrder(class_id, token_id, order_owner));
-				},
-				_ => {
-					return Err(Error::<T>::NoPermission.into());
-				},
-			}
+			let (seller, buyer) = if order.by_token_owner {
+				ensure!(price >= order.price, Error::<T>::CanNotAfford);
+				// `who` will take the order submitting by `order_owner`/`seller`
+				(order_owner.clone(), who.clone())
+			} else {
+				ensure!(price <= order.price, Error::<T>::PriceTooLow);
+				// `who`/`seller` will accept the order submitting by `order_owner`
+				(who.clone(), order_owner.clone())
+			};
+			let escrowed = EscrowedOrders::<T>::take((class_id, token_id), &order_owner).is_some();
+			Self::delete_order(class_id, token_id, &order_owner)?;
+			Self::try_delete_order(class_id, token_id, &who);
+			// `seller` transfers `order.quantity` of this NFT to `buyer`
+			Self::deliver_token(escrowed, &seller, &buyer, class_id, token_id, order.quantity)?;
+			Self::pay_for_token(order.currency_id, &buyer, &seller, class_id, order.price)?;
+			Self::deposit_event(Event::TakenOrder(class_id, token_id, order_owner));
 			Ok(().into())
 		}
 
-		/// Create an NFT order. Create only.
+		/// Create an NFT order. A bid is settled at once against the lowest ask of the same token,
+		/// currency and quantity it can afford, and an ask against the highest bid that pays at
+		/// least its price. The order is only created if nothing matches.
 		///
 		/// - `currency_id`: currency id
 		/// - `price`: price
 		/// - `category_id`: category id
 		/// - `class_id`: class id
 		/// - `token_id`: token id
+		/// - `quantity`: the quantity of the token to trade, a holder of at least `quantity` sells it, others bid for it
 		/// - `deposit`: The balances to create an order
 		/// - `deadline`: deadline
-		#[pallet::weight(100_000)]
+		#[pallet::weight(T::WeightInfo::submit_order().saturating_add(T::WeightInfo::take_order()).saturating_add(Pallet::<T>::order_scan_weight()))]
 		#[transactional]
 		pub fn submit_order(
 			origin: OriginFor<T>,
@@ -454,46 +1446,55 @@ pub mod module {
 			#[pallet::compact] category_id: CategoryIdOf<T>,
 			#[pallet::compact] class_id: ClassIdOf<T>,
 			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] quantity: QuantityOf<T>,
 			#[pallet::compact] deposit: Balance,
 			#[pallet::compact] deadline: BlockNumberOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let token: TokenInfoOf<T> = orml_nft::Module::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?;
-
-			ensure!(Self::orders((class_id, token_id), &who).is_none(), Error::<T>::DuplicatedOrder);
-			ensure!(<frame_system::Pallet<T>>::block_number() < deadline, Error::<T>::InvalidDeadline);
-			Categories::<T>::try_mutate(category_id, |maybe_category| -> DispatchResult {
-				let category = maybe_category.as_mut().ok_or(Error::<T>::CategoryNotFound)?;
-				category.nft_count = category.nft_count.saturating_add(One::one());
-				Ok(())
-			})?;
-
-			ensure!(deposit >= Self::min_order_deposit(), Error::<T>::InvalidDeposit);
-			<T as Config>::Currency::reserve(&who, deposit.saturated_into())?;
-
-			if token.owner != who {
-				ensure!(!Self::is_burnable(class_id)?, Error::<T>::Burnable); // TODO: Get ride of this limitation.
-				T::MultiCurrency::reserve(currency_id, &who, price.saturated_into())?;
+			if Self::do_submit_order(who, currency_id, price, category_id, class_id, token_id, quantity, deposit, deadline, false)? {
+				Ok(Some(T::WeightInfo::take_order().saturating_add(Self::order_scan_weight())).into())
+			} else {
+				Ok(Some(T::WeightInfo::submit_order().saturating_add(Self::order_scan_weight())).into())
 			}
+		}
 
-			let order: OrderData<T> = OrderData {
-				currency_id,
-				price,
-				deposit,
-				deadline,
-				category_id,
-			};
-			Orders::<T>::insert((class_id, token_id), &who, order);
-
-			Self::deposit_event(Event::CreatedOrder(class_id, token_id, who));
-			Ok(().into())
+		/// Create an NFT order like `submit_order`, and move the listed quantity of the token into
+		/// the escrow account until the order is taken, removed or expired. Only for sell orders.
+		///
+		/// - `currency_id`: currency id
+		/// - `price`: price
+		/// - `category_id`: category id
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `quantity`: the quantity of the token to sell
+		/// - `deposit`: The balances to create an order
+		/// - `deadline`: deadline
+		#[pallet::weight(T::WeightInfo::submit_escrowed_order().saturating_add(T::WeightInfo::take_order()).saturating_add(Pallet::<T>::order_scan_weight()))]
+		#[transactional]
+		pub fn submit_escrowed_order(
+			origin: OriginFor<T>,
+			#[pallet::compact] currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] price: Balance,
+			#[pallet::compact] category_id: CategoryIdOf<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] quantity: QuantityOf<T>,
+			#[pallet::compact] deposit: Balance,
+			#[pallet::compact] deadline: BlockNumberOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			if Self::do_submit_order(who, currency_id, price, category_id, class_id, token_id, quantity, deposit, deadline, true)? {
+				Ok(Some(T::WeightInfo::take_order().saturating_add(Self::order_scan_weight())).into())
+			} else {
+				Ok(Some(T::WeightInfo::submit_escrowed_order().saturating_add(Self::order_scan_weight())).into())
+			}
 		}
 
 		/// remove an order by order owner.
 		///
 		/// - `class_id`: class id
 		/// - `token_id`: token id
-		#[pallet::weight(100_000)]
+		#[pallet::weight(T::WeightInfo::remove_order())]
 		#[transactional]
 		pub fn remove_order(
 			origin: OriginFor<T>,
@@ -501,8 +1502,7 @@ pub mod module {
 			#[pallet::compact] token_id: TokenIdOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let token_owner = orml_nft::Module::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?.owner;
-			Self::delete_order(class_id, token_id, &who, &token_owner)?;
+			Self::delete_order(class_id, token_id, &who)?;
 			Ok(().into())
 		}
 
@@ -511,7 +1511,7 @@ pub mod module {
 		/// - `class_id`: class id
 		/// - `token_id`: token id
 		/// - `price`: price
-		#[pallet::weight(100_000)]
+		#[pallet::weight(T::WeightInfo::update_order_price())]
 		#[transactional]
 		pub fn update_order_price(
 			origin: OriginFor<T>,
@@ -522,8 +1522,9 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 			Orders::<T>::try_mutate((class_id, token_id), &who, |maybe_order| -> DispatchResult {
 				let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+				Self::ensure_currency_accepted(class_id, order.currency_id, price)?;
 
-				if !orml_nft::Module::<T>::is_owner(&who, (class_id, token_id)) {
+				if !order.by_token_owner {
 					let _ = T::MultiCurrency::unreserve(order.currency_id, &who, order.price.saturated_into());
 					T::MultiCurrency::reserve(order.currency_id, &who, price.saturated_into())?;
 				}
@@ -535,139 +1536,1389 @@ pub mod module {
 			Ok(().into())
 		}
 
-		/// Create a common category for trading NFT.
-		/// A Selling NFT should belong to a category.
+		/// Create a collection offer to buy `quantity` tokens of a class at `price` each.
+		/// `price * quantity` is reserved until the offer is filled or removed.
 		///
-		/// - `metadata`: metadata
-		#[pallet::weight(100_000)]
+		/// - `currency_id`: currency id
+		/// - `class_id`: class id
+		/// - `price`: price of every token
+		/// - `quantity`: the number of tokens to buy
+		/// - `deposit`: The balances to create an offer
+		/// - `deadline`: deadline
+		#[pallet::weight(T::WeightInfo::submit_offer())]
 		#[transactional]
-		pub fn create_category(origin: OriginFor<T>, metadata: NFTMetadata) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
+		pub fn submit_offer(
+			origin: OriginFor<T>,
+			#[pallet::compact] currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] price: Balance,
+			#[pallet::compact] quantity: QuantityOf<T>,
+			#[pallet::compact] deposit: Balance,
+			#[pallet::compact] deadline: BlockNumberOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(orml_nft::Module::<T>::classes(class_id).is_some(), Error::<T>::ClassIdNotFound);
+			ensure!(quantity >= 1, Error::<T>::InvalidQuantity);
+			ensure!(<frame_system::Pallet<T>>::block_number() < deadline, Error::<T>::InvalidDeadline);
+			Self::ensure_currency_accepted(class_id, currency_id, price)?;
 
-			let category_id = NextCategoryId::<T>::try_mutate(|id| -> Result<T::CategoryId, DispatchError> {
-				let current_id = *id;
-				*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableCategoryId)?;
-				Ok(current_id)
-			})?;
+			ensure!(deposit >= Self::min_order_deposit(), Error::<T>::InvalidDeposit);
+			<T as Config>::Currency::reserve(&who, deposit.saturated_into())?;
+			T::MultiCurrency::reserve(currency_id, &who, price.saturating_mul(quantity as Balance))?;
 
-			let info = CategoryData {
-				metadata,
-				nft_count: Default::default(),
+			let offer_id = Self::fetch_id()?;
+			let offer: OfferData<T> = OfferData {
+				owner: who.clone(),
+				currency_id,
+				price,
+				quantity,
+				deposit,
+				deadline,
 			};
-			Categories::<T>::insert(category_id, info);
+			Offers::<T>::insert(class_id, offer_id, offer);
+			OrderExpiry::<T>::insert(deadline, ExpiringOrder::Offer(class_id, offer_id), ());
 
-			Self::deposit_event(Event::CreatedCategory(category_id));
+			Self::deposit_event(Event::CreatedOffer(class_id, offer_id, who));
 			Ok(().into())
 		}
 
-		/// Update a common category.
+		/// Sell a token to a collection offer.
 		///
-		/// - `category_id`: category ID
-		/// - `metadata`: metadata
-		#[pallet::weight(100_000)]
+		/// - `class_id`: class id
+		/// - `offer_id`: offer id
+		/// - `token_id`: token id
+		/// - `price`: The min price to accept. Usually it is set to the price of the target offer.
+		#[pallet::weight(T::WeightInfo::fill_offer())]
 		#[transactional]
-		pub fn update_category(origin: OriginFor<T>, category_id: CategoryIdOf<T>, metadata: NFTMetadata) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
-			if let Some(category) = Self::categories(category_id) {
-				let info = CategoryData {
-					metadata,
-					nft_count: category.nft_count,
-				};
-				Categories::<T>::insert(category_id, info);
-				Self::deposit_event(Event::UpdatedCategory(category_id));
+		pub fn fill_offer(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] offer_id: GlobalId,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] price: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let offer: OfferData<T> = Self::offers(class_id, offer_id).ok_or(Error::<T>::OfferNotFound)?;
+			ensure!(offer.owner != who, Error::<T>::TakeOwnOrder);
+			ensure!(<frame_system::Pallet<T>>::block_number() <= offer.deadline, Error::<T>::OrderExpired);
+			ensure!(price <= offer.price, Error::<T>::PriceTooLow);
+
+			let remaining = offer.quantity.saturating_sub(1);
+			if remaining.is_zero() {
+				Offers::<T>::remove(class_id, offer_id);
+				OrderExpiry::<T>::remove(offer.deadline, ExpiringOrder::Offer(class_id, offer_id));
+				let deposit = <T as Config>::Currency::unreserve(&offer.owner, offer.deposit.saturated_into());
+				Self::deposit_event(Event::RemovedOffer(class_id, offer_id, offer.owner.clone(), deposit.saturated_into()));
+			} else {
+				Offers::<T>::insert(class_id, offer_id, OfferData { quantity: remaining, ..offer.clone() });
 			}
+
+			Self::try_delete_order(class_id, token_id, &offer.owner);
+			Self::do_transfer(&who, &offer.owner, class_id, token_id, One::one())?;
+			let _ = T::MultiCurrency::unreserve(offer.currency_id, &offer.owner, offer.price);
+			Self::pay_for_token(offer.currency_id, &offer.owner, &who, class_id, offer.price)?;
+
+			Self::deposit_event(Event::FilledOffer(class_id, offer_id, token_id, who, remaining));
 			Ok(().into())
 		}
 
-		/// Update the `MinOrderDeposit` storage.
-		#[pallet::weight(100_000)]
+		/// Remove a collection offer by its owner.
+		///
+		/// - `class_id`: class id
+		/// - `offer_id`: offer id
+		#[pallet::weight(T::WeightInfo::remove_offer())]
 		#[transactional]
-		pub fn update_min_order_deposit(origin: OriginFor<T>, new_deposit: Balance) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
-			MinOrderDeposit::<T>::mutate(|d|{
-				let old = *d;
-				*d = new_deposit;
-				Self::deposit_event(Event::UpdatedMinOrderDeposit(old, new_deposit));
-			});
+		pub fn remove_offer(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] offer_id: GlobalId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let offer: OfferData<T> = Self::offers(class_id, offer_id).ok_or(Error::<T>::OfferNotFound)?;
+			ensure!(offer.owner == who, Error::<T>::NoPermission);
+			Self::delete_offer(class_id, offer_id)?;
 			Ok(().into())
 		}
 
-		/// Create NFT class, tokens belong to the class.
+		/// Create a bundle order selling several tokens, possibly of different classes, for a single price.
+		/// All tokens are locked until the order is taken or removed.
 		///
-		/// - `metadata`: external metadata
-		/// - `properties`: class property, include `Transferable` `Burnable`
-		/// - `name`: class name, with len limitation.
-		/// - `description`: class description, with len limitation.
-		#[pallet::weight(100_000)]
+		/// - `currency_id`: currency id
+		/// - `category_id`: category id
+		/// - `price`: price of the whole bundle
+		/// - `tokens`: the tokens sold together
+		/// - `deposit`: The balances to create an order
+		/// - `deadline`: deadline
+		#[pallet::weight(T::WeightInfo::submit_bundle_order(tokens.len() as u32))]
 		#[transactional]
-		pub fn create_class(origin: OriginFor<T>, metadata: NFTMetadata, name: Vec<u8>, description: Vec<u8>, properties: Properties) -> DispatchResultWithPostInfo {
-			let who = ensure_signed(origin)?;
-			ensure!(nftmart_config::Pallet::<T>::account_whitelist(&who).is_some(), Error::<T>::AccountNotInWhitelist);
-
-			// TODO: pass constants from runtime configuration.
+		pub fn submit_bundle_order(
+			origin: OriginFor<T>,
+			#[pallet::compact] currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] category_id: CategoryIdOf<T>,
+			#[pallet::compact] price: Balance,
+			tokens: Vec<(ClassIdOf<T>, TokenIdOf<T>)>,
+			#[pallet::compact] deposit: Balance,
+			#[pallet::compact] deadline: BlockNumberOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(tokens.len() >= 2 && tokens.len() <= T::MaxBundleSize::get() as usize, Error::<T>::InvalidBundle);
+			ensure!(<frame_system::Pallet<T>>::block_number() < deadline, Error::<T>::InvalidDeadline);
+
+			let order_id = Self::fetch_id()?;
+			for &(class_id, token_id) in tokens.iter() {
+				ensure!(orml_nft::Module::<T>::is_owner(&who, (class_id, token_id)), Error::<T>::NoPermission);
+				ensure!(Self::is_transferable(class_id)?, Error::<T>::NonTransferable);
+				Self::ensure_currency_accepted(class_id, currency_id, price)?;
+				ensure!(Self::token_bundles((class_id, token_id)).is_none(), Error::<T>::InvalidBundle);
+				ensure!(Self::orders((class_id, token_id), &who).is_none(), Error::<T>::OrderExists);
+				ensure!(Self::dutch_orders((class_id, token_id)).is_none(), Error::<T>::OrderExists);
+				TokenBundles::<T>::insert((class_id, token_id), order_id);
+			}
+
+			ensure!(Categories::<T>::contains_key(category_id), Error::<T>::CategoryNotFound);
+
+			ensure!(deposit >= Self::min_order_deposit(), Error::<T>::InvalidDeposit);
+			<T as Config>::Currency::reserve(&who, deposit.saturated_into())?;
+
+			let order: BundleOrderData<T> = BundleOrderData {
+				owner: who.clone(),
+				currency_id,
+				price,
+				deposit,
+				deadline,
+				category_id,
+				tokens,
+			};
+			BundleOrders::<T>::insert(order_id, order);
+			OrderExpiry::<T>::insert(deadline, ExpiringOrder::Bundle(order_id), ());
+
+			Self::deposit_event(Event::CreatedBundleOrder(order_id, who));
+			Ok(().into())
+		}
+
+		/// Take a bundle order, all tokens of the bundle are transferred atomically.
+		///
+		/// - `order_id`: bundle order id
+		/// - `price`: The max price to take the order. Usually it is set to the price of the target order.
+		#[pallet::weight(T::WeightInfo::take_bundle_order(T::MaxBundleSize::get()))]
+		#[transactional]
+		pub fn take_bundle_order(
+			origin: OriginFor<T>,
+			#[pallet::compact] order_id: GlobalId,
+			#[pallet::compact] price: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let order: BundleOrderData<T> = Self::bundle_orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.owner != who, Error::<T>::TakeOwnOrder);
+			ensure!(<frame_system::Pallet<T>>::block_number() <= order.deadline, Error::<T>::OrderExpired);
+			ensure!(price >= order.price, Error::<T>::CanNotAfford);
+
+			let bundle_size = order.tokens.len() as u32;
+			Self::delete_bundle_order(order_id)?;
+
+			// Royalties are charged as if every token is sold at the same share of the price.
+			let count = order.tokens.len() as Balance;
+			let share = order.price / count;
+			for (i, &(class_id, token_id)) in order.tokens.iter().enumerate() {
+				Self::try_delete_order(class_id, token_id, &who);
+				Self::do_transfer(&order.owner, &who, class_id, token_id, One::one())?;
+				let token_price = if i == 0 {
+					order.price.saturating_sub(share.saturating_mul(count.saturating_sub(1)))
+				} else {
+					share
+				};
+				Self::pay_for_token(order.currency_id, &who, &order.owner, class_id, token_price)?;
+			}
+
+			Self::deposit_event(Event::TakenBundleOrder(order_id, order.owner, who));
+			Ok(Some(T::WeightInfo::take_bundle_order(bundle_size)).into())
+		}
+
+		/// Remove a bundle order by its owner, all tokens are unlocked.
+		///
+		/// - `order_id`: bundle order id
+		#[pallet::weight(T::WeightInfo::remove_bundle_order(T::MaxBundleSize::get()))]
+		#[transactional]
+		pub fn remove_bundle_order(
+			origin: OriginFor<T>,
+			#[pallet::compact] order_id: GlobalId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let order: BundleOrderData<T> = Self::bundle_orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.owner == who, Error::<T>::NoPermission);
+			Self::delete_bundle_order(order_id)?;
+			Ok(Some(T::WeightInfo::remove_bundle_order(order.tokens.len() as u32)).into())
+		}
+
+		/// Create a dutch order whose price decays from `start_price` to `floor_price`
+		/// between `start_block` and `end_block`, it expires after `end_block`. Only the token owner can create it.
+		///
+		/// - `currency_id`: currency id
+		/// - `category_id`: category id
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `start_price`: the price at `start_block`
+		/// - `floor_price`: the price at `end_block`
+		/// - `deposit`: The balances to create an order
+		/// - `start_block`: the price begins to decay since `start_block`
+		/// - `end_block`: the price reaches `floor_price` at `end_block`
+		/// - `step`: the price drops every `step` blocks, `1` means linear decay
+		#[pallet::weight(T::WeightInfo::submit_dutch_order())]
+		#[transactional]
+		pub fn submit_dutch_order(
+			origin: OriginFor<T>,
+			#[pallet::compact] currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] category_id: CategoryIdOf<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] start_price: Balance,
+			#[pallet::compact] floor_price: Balance,
+			#[pallet::compact] deposit: Balance,
+			#[pallet::compact] start_block: BlockNumberOf<T>,
+			#[pallet::compact] end_block: BlockNumberOf<T>,
+			#[pallet::compact] step: BlockNumberOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(orml_nft::Module::<T>::is_owner(&who, (class_id, token_id)), Error::<T>::NoPermission);
+			ensure!(Self::is_transferable(class_id)?, Error::<T>::NonTransferable);
+			ensure!(Self::dutch_orders((class_id, token_id)).is_none(), Error::<T>::DuplicatedOrder);
+			ensure!(Self::orders((class_id, token_id), &who).is_none(), Error::<T>::DuplicatedOrder);
+			ensure!(Self::token_bundles((class_id, token_id)).is_none(), Error::<T>::OrderExists);
+			ensure!(start_block < end_block && <frame_system::Pallet<T>>::block_number() < end_block, Error::<T>::InvalidDeadline);
+			ensure!(floor_price <= start_price, Error::<T>::InvalidDutchPrice);
+			Self::ensure_currency_accepted(class_id, currency_id, floor_price)?;
+
+			ensure!(Categories::<T>::contains_key(category_id), Error::<T>::CategoryNotFound);
+
+			ensure!(deposit >= Self::min_order_deposit(), Error::<T>::InvalidDeposit);
+			<T as Config>::Currency::reserve(&who, deposit.saturated_into())?;
+
+			let order: DutchOrderData<T> = DutchOrderData {
+				owner: who.clone(),
+				currency_id,
+				start_price,
+				floor_price,
+				start_block,
+				end_block,
+				step,
+				deposit,
+				category_id,
+			};
+			DutchOrders::<T>::insert((class_id, token_id), order);
+			OrderExpiry::<T>::insert(end_block, ExpiringOrder::Dutch((class_id, token_id)), ());
+
+			Self::deposit_event(Event::CreatedDutchOrder(class_id, token_id, who));
+			Ok(().into())
+		}
+
+		/// Take a dutch order at its current price.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `max_price`: The max price to take the order.
+		#[pallet::weight(T::WeightInfo::take_dutch_order())]
+		#[transactional]
+		pub fn take_dutch_order(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] max_price: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let order: DutchOrderData<T> = Self::dutch_orders((class_id, token_id)).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.owner != who, Error::<T>::TakeOwnOrder);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now <= order.end_block, Error::<T>::OrderExpired);
+
+			let price = order.price_at(now);
+			ensure!(price <= max_price, Error::<T>::CanNotAfford);
+
+			Self::delete_dutch_order(class_id, token_id)?;
+			Self::try_delete_order(class_id, token_id, &who);
+			Self::do_transfer(&order.owner, &who, class_id, token_id, One::one())?;
+			Self::pay_for_token(order.currency_id, &who, &order.owner, class_id, price)?;
+
+			Self::deposit_event(Event::TakenDutchOrder(class_id, token_id, order.owner, who, price));
+			Ok(().into())
+		}
+
+		/// Remove a dutch order by its owner.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		#[pallet::weight(T::WeightInfo::remove_dutch_order())]
+		#[transactional]
+		pub fn remove_dutch_order(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let order: DutchOrderData<T> = Self::dutch_orders((class_id, token_id)).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.owner == who, Error::<T>::NoPermission);
+			Self::delete_dutch_order(class_id, token_id)?;
+			Ok(().into())
+		}
+
+		/// Create an English auction. The NFT is moved into the escrow account of this pallet until the auction ends.
+		///
+		/// - `currency_id`: currency id
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `reserve_price`: the minimum price of the first bid
+		/// - `min_raise`: the minimum increment of the following bids
+		/// - `deposit`: The balances to create an auction
+		/// - `start`: bids are accepted since `start` block number
+		/// - `end`: the auction will be settled at `end` block number
+		#[pallet::weight(T::WeightInfo::create_auction().saturating_add(Pallet::<T>::auction_end_scan_weight()))]
+		#[transactional]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			#[pallet::compact] currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] reserve_price: Balance,
+			#[pallet::compact] min_raise: Balance,
+			#[pallet::compact] deposit: Balance,
+			#[pallet::compact] start: BlockNumberOf<T>,
+			#[pallet::compact] end: BlockNumberOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(start < end && <frame_system::Pallet<T>>::block_number() < end, Error::<T>::InvalidAuctionPeriod);
+			Self::ensure_currency_accepted(class_id, currency_id, reserve_price)?;
+
+			ensure!(deposit >= Self::min_order_deposit(), Error::<T>::InvalidDeposit);
+			<T as Config>::Currency::reserve(&who, deposit.saturated_into())?;
+
+			Self::do_transfer(&who, &Self::escrow_account(), class_id, token_id, One::one())?;
+
+			let auction_id = <Self as Auction<T::AccountId, BlockNumberOf<T>>>::new_auction(start, Some(end))?;
+			let item: AuctionItem<T> = AuctionItem {
+				owner: who.clone(),
+				currency_id,
+				class_id,
+				token_id,
+				reserve_price,
+				min_raise,
+				deposit,
+			};
+			AuctionItems::<T>::insert(auction_id, item);
+
+			Self::deposit_event(Event::CreatedAuction(auction_id, class_id, token_id, who));
+			Ok(().into())
+		}
+
+		/// Bid an auction. The bid amount is reserved until the bidder is outbid or the auction ends.
+		///
+		/// - `auction_id`: auction id
+		/// - `value`: bid amount
+		#[pallet::weight(T::WeightInfo::bid())]
+		#[transactional]
+		pub fn bid(
+			origin: OriginFor<T>,
+			#[pallet::compact] auction_id: GlobalId,
+			#[pallet::compact] value: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Auctions::<T>::try_mutate_exists(auction_id, |auction| -> DispatchResult {
+				let auction = auction.as_mut().ok_or(Error::<T>::AuctionNotFound)?;
+				let block_number = <frame_system::Pallet<T>>::block_number();
+
+				ensure!(block_number >= auction.start, Error::<T>::AuctionNotStarted);
+				if let Some(end) = auction.end {
+					ensure!(block_number < end, Error::<T>::AuctionExpired);
+				}
+				if let Some(ref current_bid) = auction.bid {
+					ensure!(value > current_bid.1, Error::<T>::InvalidBidPrice);
+				} else {
+					ensure!(!value.is_zero(), Error::<T>::InvalidBidPrice);
+				}
+
+				let bid_result = Self::on_new_bid(block_number, auction_id, (who.clone(), value), auction.bid.clone());
+				ensure!(bid_result.accept_bid, Error::<T>::BidNotAccepted);
+
+				if let Change::NewValue(new_end) = bid_result.auction_end_change {
+					if let Some(old_end_block) = auction.end {
+						AuctionEndTime::<T>::remove(&old_end_block, auction_id);
+					}
+					if let Some(new_end_block) = new_end {
+						Self::ensure_auction_end_available(new_end_block)?;
+						AuctionEndTime::<T>::insert(&new_end_block, auction_id, ());
+					}
+					auction.end = new_end;
+				}
+				auction.bid = Some((who.clone(), value));
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::Bid(auction_id, who, value));
+			Ok(().into())
+		}
+
+		/// Cancel an auction which has no bid yet. The NFT is returned to its owner.
+		///
+		/// - `auction_id`: auction id
+		#[pallet::weight(T::WeightInfo::cancel_auction())]
+		#[transactional]
+		pub fn cancel_auction(
+			origin: OriginFor<T>,
+			#[pallet::compact] auction_id: GlobalId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let item: AuctionItem<T> = Self::auction_items(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(item.owner == who, Error::<T>::NoPermission);
+			let auction = Self::auctions(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(auction.bid.is_none(), Error::<T>::AuctionHasBids);
+
+			<Self as Auction<T::AccountId, BlockNumberOf<T>>>::remove_auction(auction_id);
+			AuctionItems::<T>::remove(auction_id);
+			orml_nft::Module::<T>::transfer(&Self::escrow_account(), &who, (item.class_id, item.token_id), One::one())?;
+			<T as Config>::Currency::unreserve(&who, item.deposit.saturated_into());
+
+			Self::deposit_event(Event::CancelledAuction(auction_id));
+			Ok(().into())
+		}
+
+		/// List a token for rent, the lender keeps the ownership.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `currency_id`: currency id
+		/// - `price_per_block`: rent paid for every block
+		/// - `max_duration`: the maximum number of blocks of a single rental
+		#[pallet::weight(T::WeightInfo::list_rental())]
+		#[transactional]
+		pub fn list_rental(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] price_per_block: Balance,
+			#[pallet::compact] max_duration: BlockNumberOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let token = (class_id, token_id);
+			ensure!(orml_nft::Module::<T>::owner_of(token).as_ref() == Some(&who), Error::<T>::NoPermission);
+			ensure!(Self::is_transferable(class_id)?, Error::<T>::NonTransferable);
+			ensure!(Self::rental_listings(token).is_none(), Error::<T>::DuplicatedOrder);
+			ensure!(Self::orders(token, &who).is_none(), Error::<T>::OrderExists);
+			ensure!(Self::dutch_orders(token).is_none(), Error::<T>::OrderExists);
+			ensure!(Self::token_bundles(token).is_none(), Error::<T>::OrderExists);
+			ensure!(!max_duration.is_zero(), Error::<T>::InvalidRentalDuration);
+			Self::ensure_currency_accepted(class_id, currency_id, price_per_block)?;
+
+			RentalListings::<T>::insert(token, RentalListing {
+				owner: who.clone(),
+				currency_id,
+				price_per_block,
+				max_duration,
+			});
+			Self::deposit_event(Event::ListedRental(class_id, token_id, who));
+			Ok(().into())
+		}
+
+		/// Remove a rental listing, an ongoing rental lasts until its end.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		#[pallet::weight(T::WeightInfo::unlist_rental())]
+		#[transactional]
+		pub fn unlist_rental(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let listing = Self::rental_listings((class_id, token_id)).ok_or(Error::<T>::RentalNotFound)?;
+			ensure!(listing.owner == who, Error::<T>::NoPermission);
+
+			RentalListings::<T>::remove((class_id, token_id));
+			Self::deposit_event(Event::UnlistedRental(class_id, token_id));
+			Ok(().into())
+		}
+
+		/// Rent a listed token for `duration` blocks, paying the whole rent upfront to the lender.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `duration`: the number of blocks to rent
+		#[pallet::weight(T::WeightInfo::rent())]
+		#[transactional]
+		pub fn rent(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] duration: BlockNumberOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let token = (class_id, token_id);
+			let listing = Self::rental_listings(token).ok_or(Error::<T>::RentalNotFound)?;
+			ensure!(Self::user_of(class_id, token_id).is_none(), Error::<T>::RentalActive);
+			ensure!(!duration.is_zero() && duration <= listing.max_duration, Error::<T>::InvalidRentalDuration);
+
+			let rent = listing.price_per_block.saturating_mul(duration.saturated_into());
+			T::MultiCurrency::transfer(listing.currency_id, &who, &listing.owner, rent)?;
+
+			if let Some(expired) = Rentals::<T>::take(token) {
+				RentalExpiry::<T>::remove(expired.end, token);
+			}
+			let end = <frame_system::Pallet<T>>::block_number().saturating_add(duration);
+			Rentals::<T>::insert(token, RentalData {
+				lender: listing.owner,
+				user: who.clone(),
+				end,
+			});
+			RentalExpiry::<T>::insert(end, token, ());
+
+			Self::deposit_event(Event::Rented(class_id, token_id, who, end));
+			Ok(().into())
+		}
+
+		/// Lock a token in a vault and issue shares of it in a new currency to the caller.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `shares`: the number of shares issued
+		/// - `currency_id`: the currency of the reserve price
+		/// - `reserve_price`: anyone paying the reserve price buys the token out
+		#[pallet::weight(T::WeightInfo::fractionalize())]
+		#[transactional]
+		pub fn fractionalize(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] shares: Balance,
+			#[pallet::compact] currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] reserve_price: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let token = (class_id, token_id);
+			ensure!(orml_nft::Module::<T>::owner_of(token).as_ref() == Some(&who), Error::<T>::NoPermission);
+			// Holders of the shares of a bought out vault still claim their proceeds from it.
+			ensure!(!Vaults::<T>::contains_key(token), Error::<T>::VaultExists);
+			ensure!(!shares.is_zero(), Error::<T>::InvalidQuantity);
+			Self::ensure_currency_accepted(class_id, currency_id, reserve_price)?;
+
+			let quantity = orml_nft::Module::<T>::tokens_by_owner(&who, token);
+			Self::do_transfer(&who, &Self::vault_account(), class_id, token_id, quantity)?;
+
+			let share_currency_id = T::CurrencyFactory::create_currency(&Self::vault_account())?;
+			T::MultiCurrency::deposit(share_currency_id, &who, shares)?;
+			Vaults::<T>::insert(token, VaultData {
+				owner: who,
+				quantity,
+				share_currency_id,
+				shares,
+				currency_id,
+				reserve_price,
+				proceeds: None,
+			});
+
+			Self::deposit_event(Event::Fractionalized(class_id, token_id, share_currency_id, shares));
+			Ok(().into())
+		}
+
+		/// Redeem a fractionalized token, the caller must hold all shares which are burned.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		#[pallet::weight(T::WeightInfo::redeem_fractions())]
+		#[transactional]
+		pub fn redeem_fractions(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let token = (class_id, token_id);
+			let vault = Self::vaults(token).ok_or(Error::<T>::VaultNotFound)?;
+			ensure!(vault.proceeds.is_none(), Error::<T>::BoughtOut);
+			ensure!(
+				T::MultiCurrency::free_balance(vault.share_currency_id, &who) >= vault.shares,
+				Error::<T>::InsufficientShares
+			);
+
+			T::MultiCurrency::withdraw(vault.share_currency_id, &who, vault.shares)?;
+			orml_nft::Module::<T>::transfer(&Self::vault_account(), &who, token, vault.quantity)?;
+			Vaults::<T>::remove(token);
+
+			Self::deposit_event(Event::RedeemedFractions(class_id, token_id, who));
+			Ok(().into())
+		}
+
+		/// Buy out a fractionalized token at its reserve price.
+		/// Holders of shares exchange them for the proceeds pro-rata by `claim_proceeds`.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		#[pallet::weight(T::WeightInfo::buyout())]
+		#[transactional]
+		pub fn buyout(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let token = (class_id, token_id);
+			let (quantity, price) = Vaults::<T>::try_mutate(token, |maybe_vault| -> Result<_, DispatchError> {
+				let vault = maybe_vault.as_mut().ok_or(Error::<T>::VaultNotFound)?;
+				ensure!(vault.proceeds.is_none(), Error::<T>::BoughtOut);
+				T::MultiCurrency::transfer(vault.currency_id, &who, &Self::vault_account(), vault.reserve_price)?;
+				vault.proceeds = Some(vault.reserve_price);
+				Ok((vault.quantity, vault.reserve_price))
+			})?;
+			orml_nft::Module::<T>::transfer(&Self::vault_account(), &who, token, quantity)?;
+
+			Self::deposit_event(Event::BoughtOut(class_id, token_id, who, price));
+			Ok(().into())
+		}
+
+		/// Exchange all shares of the caller for their part of the buyout proceeds.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		#[pallet::weight(T::WeightInfo::claim_proceeds())]
+		#[transactional]
+		pub fn claim_proceeds(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let token = (class_id, token_id);
+			let mut vault = Self::vaults(token).ok_or(Error::<T>::VaultNotFound)?;
+			let proceeds = vault.proceeds.ok_or(Error::<T>::NotBoughtOut)?;
+			let shares = T::MultiCurrency::free_balance(vault.share_currency_id, &who).min(vault.shares);
+			ensure!(!shares.is_zero(), Error::<T>::InsufficientShares);
+
+			// The last holder gets what remains, so no dust is left in the vault.
+			let amount = multiply_by_rational(proceeds, shares, vault.shares).unwrap_or(proceeds);
+			T::MultiCurrency::withdraw(vault.share_currency_id, &who, shares)?;
+			T::MultiCurrency::transfer(vault.currency_id, &Self::vault_account(), &who, amount)?;
+
+			vault.shares = vault.shares.saturating_sub(shares);
+			vault.proceeds = Some(proceeds.saturating_sub(amount));
+			if vault.shares.is_zero() {
+				Vaults::<T>::remove(token);
+			} else {
+				Vaults::<T>::insert(token, vault);
+			}
+
+			Self::deposit_event(Event::ClaimedProceeds(class_id, token_id, who, shares, amount));
+			Ok(().into())
+		}
+
+		/// Nest a token into another one, e.g. equip an avatar. The token moves with its parent afterwards.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `parent_class_id`: class id of the parent
+		/// - `parent_token_id`: token id of the parent
+		#[pallet::weight(T::WeightInfo::nest())]
+		#[transactional]
+		pub fn nest(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] parent_class_id: ClassIdOf<T>,
+			#[pallet::compact] parent_token_id: TokenIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_transferable(class_id)?, Error::<T>::NonTransferable);
+			ensure!(
+				Self::free_quantity(&who, class_id, token_id) == orml_nft::Module::<T>::tokens_by_owner(&who, (class_id, token_id)),
+				Error::<T>::OrderExists
+			);
+
+			orml_nft::Module::<T>::nest(&who, (class_id, token_id), (parent_class_id, parent_token_id))?;
+			Self::deposit_event(Event::Nested(class_id, token_id, parent_class_id, parent_token_id));
+			Ok(().into())
+		}
+
+		/// Take a nested token out of its parent, the owner of the root token receives it.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		#[pallet::weight(T::WeightInfo::unnest())]
+		#[transactional]
+		pub fn unnest(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(orml_nft::Module::<T>::parents((class_id, token_id)).is_some(), Error::<T>::NotNested);
+			Self::take_out_nested(&who, class_id, token_id)?;
+			Ok(().into())
+		}
+
+		/// Create a common category for trading NFT.
+		/// A Selling NFT should belong to a category.
+		///
+		/// - `metadata`: metadata
+		#[pallet::weight(T::WeightInfo::create_category(metadata.len() as u32))]
+		#[transactional]
+		pub fn create_category(origin: OriginFor<T>, metadata: NFTMetadata) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let category_id = NextCategoryId::<T>::try_mutate(|id| -> Result<T::CategoryId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableCategoryId)?;
+				Ok(current_id)
+			})?;
+
+			let info = CategoryData {
+				metadata,
+				nft_count: Default::default(),
+			};
+			Categories::<T>::insert(category_id, info);
+
+			Self::deposit_event(Event::CreatedCategory(category_id));
+			Ok(().into())
+		}
+
+		/// Update a common category.
+		///
+		/// - `category_id`: category ID
+		/// - `metadata`: metadata
+		#[pallet::weight(T::WeightInfo::update_category(metadata.len() as u32))]
+		#[transactional]
+		pub fn update_category(origin: OriginFor<T>, category_id: CategoryIdOf<T>, metadata: NFTMetadata) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			if let Some(category) = Self::categories(category_id) {
+				let info = CategoryData {
+					metadata,
+					nft_count: category.nft_count,
+				};
+				Categories::<T>::insert(category_id, info);
+				Self::deposit_event(Event::UpdatedCategory(category_id));
+			}
+			Ok(().into())
+		}
+
+		/// Update the `MinOrderDeposit` storage.
+		#[pallet::weight(T::WeightInfo::update_min_order_deposit())]
+		#[transactional]
+		pub fn update_min_order_deposit(origin: OriginFor<T>, new_deposit: Balance) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			MinOrderDeposit::<T>::mutate(|d|{
+				let old = *d;
+				*d = new_deposit;
+				Self::deposit_event(Event::UpdatedMinOrderDeposit(old, new_deposit));
+			});
+			Ok(().into())
+		}
+
+		/// Update the `PlatformFeeRate` storage, the rate plus `MaxRoyaltyRate` must not exceed 100%.
+		#[pallet::weight(T::WeightInfo::update_platform_fee_rate())]
+		#[transactional]
+		pub fn update_platform_fee_rate(origin: OriginFor<T>, new_rate: Perbill) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(
+				new_rate.deconstruct().saturating_add(T::MaxRoyaltyRate::get().deconstruct()) <= Perbill::one().deconstruct(),
+				Error::<T>::PlatformFeeRateTooHigh
+			);
+			PlatformFeeRate::<T>::mutate(|r|{
+				let old = *r;
+				*r = new_rate;
+				Self::deposit_event(Event::UpdatedPlatformFeeRate(old, new_rate));
+			});
+			Ok(().into())
+		}
+
+		/// Create NFT class, tokens belong to the class.
+		///
+		/// - `metadata`: external metadata
+		/// - `properties`: class property, include `Transferable` `Burnable` `Revocable`
+		/// - `name`: class name, with len limitation.
+		/// - `description`: class description, with len limitation.
+		/// - `royalty_rate`: the royalty paid to the class beneficiary on every trade.
+		/// - `max_supply`: the maximum quantity of tokens ever minted, burned tokens included, unlimited if `None`.
+		/// - `max_mint_per_account`: the maximum quantity of tokens minted to an account, unlimited if `None`.
+		#[pallet::weight(T::WeightInfo::create_class(metadata.len() as u32))]
+		#[transactional]
+		pub fn create_class(
+			origin: OriginFor<T>,
+			metadata: NFTMetadata,
+			name: Vec<u8>,
+			description: Vec<u8>,
+			properties: Properties,
+			royalty_rate: Perbill,
+			max_supply: Option<QuantityOf<T>>,
+			max_mint_per_account: Option<QuantityOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(nftmart_config::Pallet::<T>::account_whitelist(&who).is_some(), Error::<T>::AccountNotInWhitelist);
+
+			// TODO: pass constants from runtime configuration.
+			ensure!(name.len() <= 20, Error::<T>::NameTooLong);
+			ensure!(description.len() <= 256, Error::<T>::DescriptionTooLong);
+			ensure!(
+				!properties.0.contains(ClassProperty::Revocable | ClassProperty::Transferable),
+				Error::<T>::InvalidProperties
+			);
+			ensure!(royalty_rate <= T::MaxRoyaltyRate::get(), Error::<T>::RoyaltyRateTooHigh);
+
+			let next_id = orml_nft::Module::<T>::next_class_id();
+			let owner: T::AccountId = T::ModuleId::get().into_sub_account(next_id);
+			let (deposit, all_deposit) = Self::create_class_deposit(
+				metadata.len().saturated_into(),
+				name.len().saturated_into(),
+				description.len().saturated_into(),
+			);
+
+			<T as Config>::Currency::transfer(&who, &owner, all_deposit.saturated_into(), KeepAlive)?;
+			<T as Config>::Currency::reserve(&owner, deposit.saturated_into())?;
+			// owner add proxy delegate to origin
+			<pallet_proxy::Module<T>>::add_proxy_delegate(&owner, who, Default::default(), Zero::zero())?;
+
+			let data: ClassData<BlockNumberOf<T>> = ClassData {
+				deposit,
+				properties,
+				name,
+				description,
+				create_block: <frame_system::Pallet<T>>::block_number(),
+				royalty_rate,
+				max_supply,
+				max_mint_per_account,
+			};
+			orml_nft::Module::<T>::create_class(&owner, metadata, data)?;
+
+			Self::deposit_event(Event::CreatedClass(owner, next_id));
+			Ok(().into())
+		}
+
+		/// Update the account receiving royalties of a class.
+		///
+		/// - `class_id`: class id
+		/// - `beneficiary`: the account receiving royalties
+		#[pallet::weight(T::WeightInfo::update_royalty_beneficiary())]
+		#[transactional]
+		pub fn update_royalty_beneficiary(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			RoyaltyBeneficiaries::<T>::insert(class_id, &beneficiary);
+			Self::deposit_event(Event::UpdatedRoyaltyBeneficiary(class_id, beneficiary));
+			Ok(().into())
+		}
+
+		/// Update metadata, name and description of a class.
+		/// The difference of deposit is reserved from or unreserved to the class owner.
+		///
+		/// - `class_id`: class id
+		/// - `metadata`: external metadata
+		/// - `name`: class name
+		/// - `description`: class description
+		#[pallet::weight(T::WeightInfo::update_class(metadata.len() as u32))]
+		#[transactional]
+		pub fn update_class(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			metadata: NFTMetadata,
+			name: Vec<u8>,
+			description: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
 			ensure!(name.len() <= 20, Error::<T>::NameTooLong);
 			ensure!(description.len() <= 256, Error::<T>::DescriptionTooLong);
 
-			let next_id = orml_nft::Module::<T>::next_class_id();
-			let owner: T::AccountId = T::ModuleId::get().into_sub_account(next_id);
-			let (deposit, all_deposit) = Self::create_class_deposit(
-				metadata.len().saturated_into(),
-				name.len().saturated_into(),
-				description.len().saturated_into(),
+			orml_nft::Classes::<T>::try_mutate(class_id, |maybe_class| -> DispatchResult {
+				let class_info = maybe_class.as_mut().ok_or(Error::<T>::ClassIdNotFound)?;
+				ensure!(who == class_info.owner, Error::<T>::NoPermission);
+				ensure!(!class_info.data.properties.0.contains(ClassProperty::MetadataFrozen), Error::<T>::MetadataFrozen);
+
+				let (deposit, _) = Self::create_class_deposit(
+					metadata.len().saturated_into(),
+					name.len().saturated_into(),
+					description.len().saturated_into(),
+				);
+				Self::adjust_deposit(&who, class_info.data.deposit, deposit)?;
+
+				class_info.metadata = metadata;
+				class_info.data.name = name;
+				class_info.data.description = description;
+				class_info.data.deposit = deposit;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::UpdatedClass(class_id));
+			Ok(().into())
+		}
+
+		/// Update metadata of a token.
+		/// The difference of deposit is reserved from or unreserved to the class owner.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `metadata`: external metadata
+		#[pallet::weight(T::WeightInfo::update_token_metadata(metadata.len() as u32))]
+		#[transactional]
+		pub fn update_token_metadata(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			metadata: NFTMetadata,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(!class_info.data.properties.0.contains(ClassProperty::MetadataFrozen), Error::<T>::MetadataFrozen);
+
+			orml_nft::Tokens::<T>::try_mutate(class_id, token_id, |maybe_token| -> DispatchResult {
+				let token_info = maybe_token.as_mut().ok_or(Error::<T>::TokenIdNotFound)?;
+				let quantity: Balance = token_info.quantity.saturated_into();
+				let (deposit, _) = Self::mint_token_deposit(metadata.len().saturated_into(), 1);
+				Self::adjust_deposit(
+					&who,
+					token_info.data.deposit.saturating_mul(quantity),
+					deposit.saturating_mul(quantity),
+				)?;
+
+				token_info.metadata = metadata;
+				token_info.data.deposit = deposit;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::UpdatedToken(class_id, token_id));
+			Ok(().into())
+		}
+
+		/// Freeze metadata of a class and its tokens, this can not be undone.
+		///
+		/// - `class_id`: class id
+		#[pallet::weight(T::WeightInfo::freeze_metadata())]
+		#[transactional]
+		pub fn freeze_metadata(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			orml_nft::Classes::<T>::try_mutate(class_id, |maybe_class| -> DispatchResult {
+				let class_info = maybe_class.as_mut().ok_or(Error::<T>::ClassIdNotFound)?;
+				ensure!(who == class_info.owner, Error::<T>::NoPermission);
+				class_info.data.properties.0.insert(ClassProperty::MetadataFrozen);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::FrozenMetadata(class_id));
+			Ok(().into())
+		}
+
+		/// Set an attribute of a class, the deposit is reserved from the class owner.
+		///
+		/// - `class_id`: class id
+		/// - `key`: attribute key
+		/// - `value`: attribute value
+		#[pallet::weight(T::WeightInfo::set_class_attribute())]
+		#[transactional]
+		pub fn set_class_attribute(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			key: Vec<u8>,
+			value: AttributeValue,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_attribute_editable(&who, class_id)?;
+
+			let deposit = Self::attribute_deposit(&key, &value)?;
+			let old = Self::class_attributes(class_id, &key).map(|(_, d)| d).unwrap_or_default();
+			Self::adjust_deposit(&who, old, deposit)?;
+			ClassAttributes::<T>::insert(class_id, &key, (value, deposit));
+
+			Self::deposit_event(Event::SetClassAttribute(class_id, key));
+			Ok(().into())
+		}
+
+		/// Clear an attribute of a class, the deposit is unreserved to the class owner.
+		///
+		/// - `class_id`: class id
+		/// - `key`: attribute key
+		#[pallet::weight(T::WeightInfo::clear_class_attribute())]
+		#[transactional]
+		pub fn clear_class_attribute(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			key: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_attribute_editable(&who, class_id)?;
+
+			let (_, deposit) = ClassAttributes::<T>::take(class_id, &key).ok_or(Error::<T>::AttributeNotFound)?;
+			<T as Config>::Currency::unreserve(&who, deposit.saturated_into());
+
+			Self::deposit_event(Event::ClearedClassAttribute(class_id, key));
+			Ok(().into())
+		}
+
+		/// Set an attribute of a token, the deposit is reserved from the class owner.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `key`: attribute key
+		/// - `value`: attribute value
+		#[pallet::weight(T::WeightInfo::set_token_attribute())]
+		#[transactional]
+		pub fn set_token_attribute(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			key: Vec<u8>,
+			value: AttributeValue,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_attribute_editable(&who, class_id)?;
+			ensure!(orml_nft::Module::<T>::tokens(class_id, token_id).is_some(), Error::<T>::TokenIdNotFound);
+
+			let deposit = Self::attribute_deposit(&key, &value)?;
+			let old = Self::token_attributes((class_id, token_id), &key).map(|(_, d)| d).unwrap_or_default();
+			Self::adjust_deposit(&who, old, deposit)?;
+			TokenAttributes::<T>::insert((class_id, token_id), &key, (value, deposit));
+
+			Self::deposit_event(Event::SetTokenAttribute(class_id, token_id, key));
+			Ok(().into())
+		}
+
+		/// Clear an attribute of a token, the deposit is unreserved to the class owner.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `key`: attribute key
+		#[pallet::weight(T::WeightInfo::clear_token_attribute())]
+		#[transactional]
+		pub fn clear_token_attribute(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			key: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_attribute_editable(&who, class_id)?;
+
+			let (_, deposit) = TokenAttributes::<T>::take((class_id, token_id), &key).ok_or(Error::<T>::AttributeNotFound)?;
+			<T as Config>::Currency::unreserve(&who, deposit.saturated_into());
+
+			Self::deposit_event(Event::ClearedTokenAttribute(class_id, token_id, key));
+			Ok(().into())
+		}
+
+		/// Restrict the trades of tokens of a class to some accepted currencies, an empty list
+		/// lifts the restriction. The deposit is reserved from the class owner.
+		///
+		/// - `class_id`: class id
+		/// - `currencies`: the currencies accepted for tokens of this class
+		#[pallet::weight(T::WeightInfo::set_class_currencies(currencies.len() as u32))]
+		#[transactional]
+		pub fn set_class_currencies(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			currencies: Vec<CurrencyIdOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(currencies.len() <= T::MaxClassCurrencies::get() as usize, Error::<T>::TooManyCurrencies);
+			for currency_id in currencies.iter() {
+				ensure!(nftmart_config::Pallet::<T>::min_order_price(*currency_id).is_some(), Error::<T>::CurrencyNotAccepted);
+			}
+
+			let old = Self::class_currencies(class_id).map(|(_, d)| d).unwrap_or_default();
+			if currencies.is_empty() {
+				Self::adjust_deposit(&who, old, Zero::zero())?;
+				ClassCurrencies::<T>::remove(class_id);
+			} else {
+				let deposit = (currencies.encoded_size() as Balance).saturating_mul(T::MetaDataByteDeposit::get());
+				Self::adjust_deposit(&who, old, deposit)?;
+				ClassCurrencies::<T>::insert(class_id, (currencies, deposit));
+			}
+
+			Self::deposit_event(Event::UpdatedClassCurrencies(class_id));
+			Ok(().into())
+		}
+
+		/// Burn a token of a revocable class without the consent of its owner, e.g. invalidate a certificate.
+		/// The deposit of the token is unreserved to the class owner.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `owner`: the current owner of the token
+		/// - `quantity`: the quantity to revoke
+		#[pallet::weight(T::WeightInfo::revoke())]
+		#[transactional]
+		pub fn revoke(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			owner: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] quantity: QuantityOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			Self::ensure_revocable(&who, class_id, token_id, &owner, quantity)?;
+
+			let token_info = orml_nft::Module::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?;
+			orml_nft::Module::<T>::burn(&owner, (class_id, token_id), quantity)?;
+			let deposit: Balance = token_info.data.deposit.saturating_mul(quantity.saturated_into());
+			<T as Config>::Currency::unreserve(&who, deposit.saturated_into());
+			Self::remove_burned_token_attributes(class_id, token_id);
+
+			Self::deposit_event(Event::RevokedToken(class_id, token_id, owner, quantity));
+			Ok(().into())
+		}
+
+		/// Move a token of a revocable class to another account without the consent of its owner,
+		/// e.g. reissue a membership to a new wallet.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `from`: the current owner of the token
+		/// - `to`: the new owner
+		/// - `quantity`: the quantity to reassign
+		#[pallet::weight(T::WeightInfo::reassign())]
+		#[transactional]
+		pub fn reassign(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			from: <T::Lookup as StaticLookup>::Source,
+			to: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] quantity: QuantityOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(to)?;
+			Self::ensure_revocable(&who, class_id, token_id, &from, quantity)?;
+
+			orml_nft::Module::<T>::transfer(&from, &to, (class_id, token_id), quantity)?;
+
+			Self::deposit_event(Event::ReassignedToken(class_id, token_id, from, to, quantity));
+			Ok(().into())
+		}
+
+		/// Add an admin to a class, the origin must be the class owner.
+		/// The class owner reserves the proxy deposit, see `add_class_admin_deposit`.
+		///
+		/// - `class_id`: class id
+		/// - `admin`: the account granted the role
+		/// - `role`: what the admin is allowed to do on behalf of the class
+		#[pallet::weight(T::WeightInfo::add_class_admin())]
+		#[transactional]
+		pub fn add_class_admin(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			admin: <T::Lookup as StaticLookup>::Source,
+			role: ClassAdminRole,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let admin = T::Lookup::lookup(admin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			<pallet_proxy::Module<T>>::add_proxy_delegate(&who, admin.clone(), T::ClassAdminProxy::convert(role), Zero::zero())?;
+
+			Self::deposit_event(Event::AddedClassAdmin(class_id, admin, role));
+			Ok(().into())
+		}
+
+		/// Remove an admin from a class, the origin must be the class owner.
+		///
+		/// - `class_id`: class id
+		/// - `admin`: the account losing the role
+		/// - `role`: the role granted by `add_class_admin`
+		#[pallet::weight(T::WeightInfo::remove_class_admin())]
+		#[transactional]
+		pub fn remove_class_admin(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			admin: <T::Lookup as StaticLookup>::Source,
+			role: ClassAdminRole,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let admin = T::Lookup::lookup(admin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			let proxy_type = T::ClassAdminProxy::convert(role);
+			// The creator of a class holds the default proxy type, which is a full admin as well.
+			let full_admin = T::ClassAdminProxy::convert(ClassAdminRole::Admin);
+			ensure!(
+				<pallet_proxy::Module<T>>::proxies(&who).0.iter().any(|proxy| {
+					(proxy.proxy_type == Default::default() || proxy.proxy_type == full_admin) &&
+						!(proxy.delegate == admin && proxy.proxy_type == proxy_type)
+				}),
+				Error::<T>::CannotRemoveLastAdmin
 			);
 
-			<T as Config>::Currency::transfer(&who, &owner, all_deposit.saturated_into(), KeepAlive)?;
-			<T as Config>::Currency::reserve(&owner, deposit.saturated_into())?;
-			// owner add proxy delegate to origin
-			<pallet_proxy::Module<T>>::add_proxy_delegate(&owner, who, Default::default(), Zero::zero())?;
+			<pallet_proxy::Module<T>>::remove_proxy_delegate(&who, admin.clone(), proxy_type, Zero::zero())?;
 
-			let data: ClassData<BlockNumberOf<T>> = ClassData {
-				deposit,
-				properties,
-				name,
-				description,
-				create_block: <frame_system::Pallet<T>>::block_number(),
-			};
-			orml_nft::Module::<T>::create_class(&owner, metadata, data)?;
+			Self::deposit_event(Event::RemovedClassAdmin(class_id, admin, role));
+			Ok(().into())
+		}
+
+		/// Mint NFT token
+		///
+		/// - `to`: the token owner's account
+		/// - `class_id`: token belong to the class id
+		/// - `metadata`: external metadata
+		/// - `quantity`: token quantity
+		#[pallet::weight(T::WeightInfo::mint(metadata.len() as u32))]
+		#[transactional]
+		pub fn mint(
+			origin: OriginFor<T>,
+			to: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			metadata: NFTMetadata,
+			#[pallet::compact] quantity: QuantityOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			ensure!(nftmart_config::Pallet::<T>::account_whitelist(&to).is_some(), Error::<T>::AccountNotInWhitelist);
+
+			ensure!(quantity >= 1, Error::<T>::InvalidQuantity);
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			Self::do_mint(&to, class_id, &class_info, metadata, quantity)?;
+			Ok(().into())
+		}
+
+		/// Configure a drop, any signed account can mint tokens of the class by `public_mint` afterwards.
+		/// A deposit per byte of the drop is reserved from the class owner, it replaces the deposit of
+		/// the previous drop.
+		///
+		/// - `class_id`: class id
+		/// - `drop`: price, sale period, per wallet limit, allowlist and metadata of minted tokens
+		#[pallet::weight(T::WeightInfo::set_drop(drop.metadata.len() as u32))]
+		#[transactional]
+		pub fn set_drop(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			drop: DropData<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(drop.start < drop.end, Error::<T>::InvalidDropPeriod);
+			ensure!(drop.metadata.len() <= T::MaxDropMetadataLength::get() as usize, Error::<T>::DropMetadataTooLong);
+			Self::ensure_currency_accepted(class_id, drop.currency_id, drop.price)?;
+
+			let old = Self::drops(class_id).map(|(_, d)| d).unwrap_or_default();
+			let deposit = (drop.encoded_size() as Balance).saturating_mul(T::MetaDataByteDeposit::get());
+			Self::adjust_deposit(&who, old, deposit)?;
+			Drops::<T>::insert(class_id, (drop, deposit));
+			Self::deposit_event(Event::SetDrop(class_id));
+			Ok(().into())
+		}
+
+		/// Remove the drop of a class, accounts minted before keep their tokens.
+		/// The deposit of the drop is unreserved to the class owner.
+		///
+		/// - `class_id`: class id
+		#[pallet::weight(T::WeightInfo::remove_drop())]
+		#[transactional]
+		pub fn remove_drop(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			let (_, deposit) = Drops::<T>::take(class_id).ok_or(Error::<T>::DropNotFound)?;
+			<T as Config>::Currency::unreserve(&who, deposit.saturated_into());
+			DropMinted::<T>::remove_prefix(class_id);
+
+			Self::deposit_event(Event::RemovedDrop(class_id));
+			Ok(().into())
+		}
+
+		/// Redeem a mint voucher, minting the token to the caller and paying the signer.
+		/// The caller pays the token deposit as well, it is refunded on burn.
+		///
+		/// - `voucher`: the voucher signed by a minter of the class
+		/// - `metadata`: metadata of the token, its hash must match the voucher
+		/// - `signature`: signature of the voucher payload, see `voucher_payload`
+		#[pallet::weight(T::WeightInfo::redeem_voucher(metadata.len() as u32))]
+		#[transactional]
+		pub fn redeem_voucher(
+			origin: OriginFor<T>,
+			voucher: MintVoucher<T>,
+			metadata: NFTMetadata,
+			signature: T::OffchainSignature,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(nftmart_config::Pallet::<T>::account_whitelist(&who).is_some(), Error::<T>::AccountNotInWhitelist);
+
+			ensure!(voucher.quantity >= 1, Error::<T>::InvalidQuantity);
+			ensure!(signature.verify(&Self::voucher_payload(&voucher)[..], &voucher.signer), Error::<T>::InvalidVoucher);
+			ensure!(<frame_system::Pallet<T>>::block_number() <= voucher.expiry, Error::<T>::VoucherExpired);
+			ensure!(BlakeTwo256::hash(&metadata) == voucher.metadata_hash, Error::<T>::MetadataMismatch);
+
+			let class_id = voucher.class_id;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			let minter = T::ClassAdminProxy::convert(ClassAdminRole::Minter);
+			ensure!(
+				<pallet_proxy::Module<T>>::has_proxy_delegate(&class_info.owner, &voucher.signer, &minter),
+				Error::<T>::InvalidVoucher
+			);
+			RedeemedVouchers::<T>::try_mutate(class_id, voucher.nonce, |redeemed| -> DispatchResult {
+				ensure!(redeemed.is_none(), Error::<T>::VoucherRedeemed);
+				*redeemed = Some(());
+				Ok(())
+			})?;
 
-			Self::deposit_event(Event::CreatedClass(owner, next_id));
+			T::MultiCurrency::transfer(voucher.currency_id, &who, &voucher.signer, voucher.price)?;
+			let total_deposit = Self::mint_token_deposit(metadata.len().saturated_into(), voucher.quantity).1;
+			<T as Config>::Currency::transfer(&who, &class_info.owner, total_deposit.saturated_into(), KeepAlive)?;
+			Self::do_mint(&who, class_id, &class_info, metadata, voucher.quantity)?;
+
+			Self::deposit_event(Event::RedeemedVoucher(class_id, voucher.nonce, voucher.signer, who));
 			Ok(().into())
 		}
 
-		/// Mint NFT token
+		/// Mint tokens of a class with a drop, paying the drop price to the class owner.
+		/// The caller pays the token deposit as well, it is refunded on burn.
 		///
-		/// - `to`: the token owner's account
-		/// - `class_id`: token belong to the class id
-		/// - `metadata`: external metadata
+		/// - `class_id`: class id
 		/// - `quantity`: token quantity
-		#[pallet::weight(100_000)]
+		/// - `proof`: Merkle proof of the caller in the allowlist, empty if there is no allowlist
+		#[pallet::weight(T::WeightInfo::public_mint(proof.len() as u32))]
 		#[transactional]
-		pub fn mint(
+		pub fn public_mint(
 			origin: OriginFor<T>,
-			to: <T::Lookup as StaticLookup>::Source,
 			#[pallet::compact] class_id: ClassIdOf<T>,
-			metadata: NFTMetadata,
-			#[pallet::compact] quantity: u32,
+			#[pallet::compact] quantity: QuantityOf<T>,
+			proof: Vec<H256>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let to = T::Lookup::lookup(to)?;
-			ensure!(nftmart_config::Pallet::<T>::account_whitelist(&to).is_some(), Error::<T>::AccountNotInWhitelist);
+			ensure!(nftmart_config::Pallet::<T>::account_whitelist(&who).is_some(), Error::<T>::AccountNotInWhitelist);
 
 			ensure!(quantity >= 1, Error::<T>::InvalidQuantity);
+			ensure!(proof.len() <= T::MaxProofLength::get() as usize, Error::<T>::ProofTooLong);
 			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
-			ensure!(who == class_info.owner, Error::<T>::NoPermission);
-			let (deposit, total_deposit) = Self::mint_token_deposit(metadata.len().saturated_into(), quantity);
+			let (drop, _) = Self::drops(class_id).ok_or(Error::<T>::DropNotFound)?;
 
-			<T as Config>::Currency::reserve(&class_info.owner, total_deposit.saturated_into())?;
-			let data: TokenData<BlockNumberOf<T>> = TokenData {
-				deposit,
-				create_block: <frame_system::Pallet<T>>::block_number(),
-			};
-			for _ in 0..quantity {
-				orml_nft::Module::<T>::mint(&to, class_id, metadata.clone(), data.clone())?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(drop.start <= now && now < drop.end, Error::<T>::DropNotActive);
+			if let Some(root) = drop.allowlist {
+				ensure!(Self::verify_allowlist(root, &who, &proof), Error::<T>::NotInAllowlist);
 			}
+			DropMinted::<T>::try_mutate(class_id, &who, |minted| -> DispatchResult {
+				*minted = minted.saturating_add(quantity);
+				if let Some(max_per_wallet) = drop.max_per_wallet {
+					ensure!(*minted <= max_per_wallet, Error::<T>::ExceedMaxPerWallet);
+				}
+				Ok(())
+			})?;
 
-			Self::deposit_event(Event::MintedToken(who, to, class_id, quantity));
+			let price = drop.price.saturating_mul(quantity.into());
+			T::MultiCurrency::transfer(drop.currency_id, &who, &class_info.owner, price)?;
+			let total_deposit = Self::mint_token_deposit(drop.metadata.len().saturated_into(), quantity).1;
+			<T as Config>::Currency::transfer(&who, &class_info.owner, total_deposit.saturated_into(), KeepAlive)?;
+			Self::do_mint(&who, class_id, &class_info, drop.metadata, quantity)?;
 			Ok(().into())
 		}
 
@@ -676,17 +2927,84 @@ pub mod module {
 		/// - `to`: the token owner's account
 		/// - `class_id`: class id
 		/// - `token_id`: token id
-		#[pallet::weight(100_000)]
+		/// - `quantity`: the quantity to transfer
+		#[pallet::weight(T::WeightInfo::transfer())]
 		#[transactional]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			to: <T::Lookup as StaticLookup>::Source,
 			#[pallet::compact] class_id: ClassIdOf<T>,
 			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] quantity: QuantityOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			Self::do_transfer(&who, &to, class_id, token_id, quantity)?;
+			Ok(().into())
+		}
+
+		/// Transfer NFT token on behalf of its owner, the caller must be approved by `from`.
+		///
+		/// - `from`: the token owner's account
+		/// - `to`: the receiver's account
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `quantity`: the quantity to transfer
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		#[transactional]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: <T::Lookup as StaticLookup>::Source,
+			to: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] quantity: QuantityOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
 			let to = T::Lookup::lookup(to)?;
-			Self::do_transfer(&who, &to, class_id, token_id)?;
+			ensure!(orml_nft::Module::<T>::is_approved(&who, &from, (class_id, token_id)), Error::<T>::NoPermission);
+			Self::do_transfer(&from, &to, class_id, token_id, quantity)?;
+			Ok(().into())
+		}
+
+		/// Approve an account to transfer a token of the caller, the approval is cleared
+		/// once the caller transfers or burns the token.
+		///
+		/// - `spender`: the approved account, `None` removes the approval
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		#[pallet::weight(T::WeightInfo::approve())]
+		#[transactional]
+		pub fn approve(
+			origin: OriginFor<T>,
+			spender: Option<<T::Lookup as StaticLookup>::Source>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let spender = spender.map(T::Lookup::lookup).transpose()?;
+			ensure!(orml_nft::Module::<T>::tokens(class_id, token_id).is_some(), Error::<T>::TokenIdNotFound);
+			orml_nft::Module::<T>::approve(&who, spender.clone(), (class_id, token_id))?;
+			Self::deposit_event(Event::Approval(who, class_id, token_id, spender));
+			Ok(().into())
+		}
+
+		/// Approve or disapprove an operator to transfer all tokens of the caller.
+		///
+		/// - `operator`: the operator's account
+		/// - `approved`: approve or disapprove
+		#[pallet::weight(T::WeightInfo::set_approval_for_all())]
+		#[transactional]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			operator: <T::Lookup as StaticLookup>::Source,
+			approved: bool,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let operator = T::Lookup::lookup(operator)?;
+			orml_nft::Module::<T>::set_approval_for_all(&who, &operator, approved);
+			Self::deposit_event(Event::ApprovalForAll(who, operator, approved));
 			Ok(().into())
 		}
 
@@ -694,29 +3012,33 @@ pub mod module {
 		///
 		/// - `class_id`: class id
 		/// - `token_id`: token id
-		#[pallet::weight(100_000)]
+		/// - `quantity`: the quantity to burn
+		#[pallet::weight(T::WeightInfo::burn())]
 		#[transactional]
 		pub fn burn(
 			origin: OriginFor<T>,
 			#[pallet::compact] class_id: ClassIdOf<T>,
 			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] quantity: QuantityOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::is_burnable(class_id)?, Error::<T>::NonBurnable);
 
 			let token_info = orml_nft::Module::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?;
-			ensure!(who == token_info.owner, Error::<T>::NoPermission);
+			Self::take_out_nested(&who, class_id, token_id)?;
+			ensure!(orml_nft::Module::<T>::tokens_by_owner(&who, (class_id, token_id)) >= quantity, Error::<T>::NoPermission);
+			ensure!(Self::free_quantity(&who, class_id, token_id) >= quantity, Error::<T>::OrderExists);
 
-			ensure!(Self::orders((class_id, token_id), &who).is_none(), Error::<T>::OrderExists);
-			orml_nft::Module::<T>::burn(&who, (class_id, token_id))?;
+			orml_nft::Module::<T>::burn(&who, (class_id, token_id), quantity)?;
+			Self::remove_burned_token_attributes(class_id, token_id);
 			let owner: T::AccountId = T::ModuleId::get().into_sub_account(class_id);
-			let data = token_info.data;
+			let deposit: Balance = token_info.data.deposit.saturating_mul(quantity.saturated_into());
 			// `repatriate_reserved` will check `to` account exist and return `DeadAccount`.
 			// `transfer` not do this check.
-			<T as Config>::Currency::unreserve(&owner, data.deposit.saturated_into());
-			<T as Config>::Currency::transfer(&owner, &who, data.deposit.saturated_into(), KeepAlive)?;
+			<T as Config>::Currency::unreserve(&owner, deposit.saturated_into());
+			<T as Config>::Currency::transfer(&owner, &who, deposit.saturated_into(), KeepAlive)?;
 
-			Self::deposit_event(Event::BurnedToken(who, class_id, token_id));
+			Self::deposit_event(Event::BurnedToken(who, class_id, token_id, quantity));
 			Ok(().into())
 		}
 
@@ -724,7 +3046,7 @@ pub mod module {
 		///
 		/// - `class_id`: destroy class id
 		/// - `dest`: transfer reserve balance from sub_account to dest
-		#[pallet::weight(100_000)]
+		#[pallet::weight(T::WeightInfo::destroy_class())]
 		#[transactional]
 		pub fn destroy_class(
 			origin: OriginFor<T>,
@@ -745,11 +3067,25 @@ pub mod module {
 			// `repatriate_reserved` will check `to` account exist and return `DeadAccount`.
 			// `transfer` not do this check.
 			<T as Config>::Currency::unreserve(&owner, data.deposit.saturated_into());
+			for (_, (_, deposit)) in ClassAttributes::<T>::drain_prefix(class_id) {
+				<T as Config>::Currency::unreserve(&owner, deposit.saturated_into());
+			}
+			if let Some((_, deposit)) = ClassCurrencies::<T>::take(class_id) {
+				<T as Config>::Currency::unreserve(&owner, deposit.saturated_into());
+			}
+			if let Some((_, deposit)) = Drops::<T>::take(class_id) {
+				<T as Config>::Currency::unreserve(&owner, deposit.saturated_into());
+			}
 			// At least there is one admin at this point.
 			<T as Config>::Currency::transfer(&owner, &dest, data.deposit.saturated_into(), KeepAlive)?;
 
 			// transfer all free from origin to dest
 			orml_nft::Module::<T>::destroy_class(&who, class_id)?;
+			RoyaltyBeneficiaries::<T>::remove(class_id);
+			MintedByClass::<T>::remove(class_id);
+			MintedByAccount::<T>::remove_prefix(class_id);
+			DropMinted::<T>::remove_prefix(class_id);
+			RedeemedVouchers::<T>::remove_prefix(class_id);
 
 			Self::deposit_event(Event::DestroyedClass(who, class_id, dest));
 			Ok(().into())
@@ -759,19 +3095,276 @@ pub mod module {
 
 impl<T: Config> Pallet<T> {
 
+	/// The account which holds NFTs of ongoing auctions.
+	pub fn escrow_account() -> T::AccountId {
+		T::ModuleId::get().into_sub_account(*b"auction")
+	}
+
+	/// The account which holds fractionalized NFTs, owns their share currencies and holds buyout proceeds.
+	pub fn vault_account() -> T::AccountId {
+		T::ModuleId::get().into_sub_account(*b"vault")
+	}
+
+	fn fetch_id() -> Result<GlobalId, DispatchError> {
+		NextId::<T>::try_mutate(|id| -> Result<GlobalId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(One::one()).ok_or(Error::<T>::NoAvailableId)?;
+			Ok(current_id)
+		})
+	}
+
+	/// Check another auction can end at `end`, so that settling all auctions of a block is bounded.
+	fn ensure_auction_end_available(end: BlockNumberOf<T>) -> DispatchResult {
+		ensure!(
+			AuctionEndTime::<T>::iter_prefix(end).count() < T::MaxAuctionsEndingPerBlock::get() as usize,
+			Error::<T>::TooManyEndingAuctions
+		);
+		Ok(())
+	}
+
+	/// Pay the winner's bid to the seller and deliver the escrowed NFT to the winner.
+	#[transactional]
+	fn settle_auction(item: &AuctionItem<T>, winner: &T::AccountId, price: Balance) -> DispatchResult {
+		let _ = T::MultiCurrency::unreserve(item.currency_id, winner, price);
+		Self::pay_for_token(item.currency_id, winner, &item.owner, item.class_id, price)?;
+		orml_nft::Module::<T>::transfer(&Self::escrow_account(), winner, (item.class_id, item.token_id), One::one())?;
+		Ok(())
+	}
+
+	/// The account receiving royalties of `class_id`.
+	pub fn royalty_beneficiary(class_id: ClassIdOf<T>) -> Option<T::AccountId> {
+		Self::royalty_beneficiaries(class_id).or_else(|| orml_nft::Module::<T>::classes(class_id).map(|c| c.owner))
+	}
+
+	/// Split `price` of a token of `class_id` into `(platform_fee, royalty, seller_proceeds)`.
+	pub fn trade_proceeds(class_id: ClassIdOf<T>, price: Balance) -> (Balance, Balance, Balance) {
+		let platform_fee: Balance = Self::platform_fee_rate() * price;
+		// Rounding may push the sum of both rates over `price`, the royalty gives way.
+		let royalty: Balance = orml_nft::Module::<T>::classes(class_id)
+			.map_or(Zero::zero(), |class_info| class_info.data.royalty_rate * price)
+			.min(price.saturating_sub(platform_fee));
+		let proceeds = price.saturating_sub(platform_fee).saturating_sub(royalty);
+		(platform_fee, royalty, proceeds)
+	}
+
+	/// `buyer` pays `price` for a token of `class_id`, the platform fee and the class royalty
+	/// are deducted from `price`, and the rest goes to `seller`.
+	fn pay_for_token(
+		currency_id: CurrencyIdOf<T>,
+		buyer: &T::AccountId,
+		seller: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		price: Balance,
+	) -> DispatchResult {
+		let (platform_fee, royalty, proceeds) = Self::trade_proceeds(class_id, price);
+		if !platform_fee.is_zero() {
+			T::MultiCurrency::transfer(currency_id, buyer, &T::PlatformFeeAccount::get(), platform_fee)?;
+			Self::deposit_event(Event::PlatformFeePaid(class_id, currency_id, platform_fee));
+		}
+		if !royalty.is_zero() {
+			let beneficiary = Self::royalty_beneficiary(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			T::MultiCurrency::transfer(currency_id, buyer, &beneficiary, royalty)?;
+			Self::deposit_event(Event::RoyaltyPaid(class_id, beneficiary, currency_id, royalty));
+		}
+		T::MultiCurrency::transfer(currency_id, buyer, seller, proceeds)?;
+		Ok(())
+	}
+
+	fn is_transferable(class_id: ClassIdOf<T>) -> Result<bool, DispatchError> {
+		let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+		let data = class_info.data;
+		Ok(data.properties.0.contains(ClassProperty::Transferable))
+	}
+
+	/// Reserve or unreserve the difference between the `old` and `new` deposit of `who`.
+	fn adjust_deposit(who: &T::AccountId, old: Balance, new: Balance) -> DispatchResult {
+		if new > old {
+			<T as Config>::Currency::reserve(who, new.saturating_sub(old).saturated_into())?;
+		} else {
+			<T as Config>::Currency::unreserve(who, old.saturating_sub(new).saturated_into());
+		}
+		Ok(())
+	}
+
 	fn is_burnable(class_id: ClassIdOf<T>) -> Result<bool, DispatchError> {
 		let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
 		let data = class_info.data;
 		Ok(data.properties.0.contains(ClassProperty::Burnable))
 	}
 
-	fn delete_order(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>, who: &T::AccountId, token_owner: &T::AccountId) -> DispatchResult {
+	/// Create an order of `who`, or settle it at once against the best matching order.
+	/// Returns whether it has been settled.
+	#[allow(clippy::too_many_arguments)]
+	fn do_submit_order(
+		who: T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		price: Balance,
+		category_id: CategoryIdOf<T>,
+		class_id: ClassIdOf<T>,
+		token_id: TokenIdOf<T>,
+		quantity: QuantityOf<T>,
+		deposit: Balance,
+		deadline: BlockNumberOf<T>,
+		escrow: bool,
+	) -> Result<bool, DispatchError> {
+		let token: orml_nft::TokenInfoOf<T> = orml_nft::Module::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?;
+		ensure!(!quantity.is_zero() && quantity <= token.quantity, Error::<T>::InvalidQuantity);
+
+		ensure!(Self::orders((class_id, token_id), &who).is_none(), Error::<T>::DuplicatedOrder);
+		ensure!(<frame_system::Pallet<T>>::block_number() < deadline, Error::<T>::InvalidDeadline);
+		ensure!(Categories::<T>::contains_key(category_id), Error::<T>::CategoryNotFound);
+		ensure!(deposit >= Self::min_order_deposit(), Error::<T>::InvalidDeposit);
+		Self::ensure_currency_accepted(class_id, currency_id, price)?;
+
+		let by_token_owner = orml_nft::Module::<T>::tokens_by_owner(&who, (class_id, token_id)) >= quantity;
+		ensure!(!escrow || by_token_owner, Error::<T>::CannotEscrowBid);
+		if !by_token_owner {
+			ensure!(!Self::is_burnable(class_id)?, Error::<T>::Burnable); // TODO: Get ride of this limitation.
+		} else {
+			ensure!(Self::is_transferable(class_id)?, Error::<T>::NonTransferable);
+			ensure!(Self::free_quantity(&who, class_id, token_id) >= quantity, Error::<T>::OrderExists);
+		}
+
+		if let Some((order_owner, order)) = Self::best_match(class_id, token_id, currency_id, price, quantity, by_token_owner, &who) {
+			let (seller, buyer) = if by_token_owner {
+				(who.clone(), order_owner.clone())
+			} else {
+				(order_owner.clone(), who.clone())
+			};
+			let escrowed = EscrowedOrders::<T>::take((class_id, token_id), &order_owner).is_some();
+			Self::delete_order(class_id, token_id, &order_owner)?;
+			// The matched order was placed first, so it sets the price.
+			Self::deliver_token(escrowed, &seller, &buyer, class_id, token_id, quantity)?;
+			Self::pay_for_token(currency_id, &buyer, &seller, class_id, order.price)?;
+			Self::deposit_event(Event::TakenOrder(class_id, token_id, order_owner));
+			return Ok(true);
+		}
+		ensure!(
+			Orders::<T>::iter_prefix((class_id, token_id)).count() < T::MaxOrdersPerToken::get() as usize,
+			Error::<T>::TooManyOrders
+		);
+
+		Categories::<T>::mutate(category_id, |maybe_category| {
+			maybe_category.as_mut().map(|category| category.nft_count = category.nft_count.saturating_add(One::one()));
+		});
+		<T as Config>::Currency::reserve(&who, deposit.saturated_into())?;
+		if !by_token_owner {
+			T::MultiCurrency::reserve(currency_id, &who, price.saturated_into())?;
+		}
+
+		if escrow {
+			Self::do_transfer(&who, &Self::order_escrow_account(), class_id, token_id, quantity)?;
+			EscrowedOrders::<T>::insert((class_id, token_id), &who, ());
+			Self::deposit_event(Event::EscrowedToken(class_id, token_id, who.clone(), quantity));
+		}
+
+		let order: OrderData<T> = OrderData {
+			currency_id,
+			price,
+			deposit,
+			deadline,
+			category_id,
+			quantity,
+			by_token_owner,
+		};
+		Orders::<T>::insert((class_id, token_id), &who, order);
+		OrderExpiry::<T>::insert(deadline, ExpiringOrder::Order((class_id, token_id), who.clone()), ());
+		OrdersByAccount::<T>::insert(&who, (class_id, token_id), ());
+		OrdersByCategory::<T>::insert(category_id, ((class_id, token_id), &who), ());
+
+		Self::deposit_event(Event::CreatedOrder(class_id, token_id, who));
+		Ok(false)
+	}
+
+	/// The payload signed by the minter of `voucher`, tagged and bound to this chain by its genesis hash
+	/// so that the signature is not valid for any other message or chain.
+	pub fn voucher_payload(voucher: &MintVoucher<T>) -> Vec<u8> {
+		let genesis_hash = <frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+		(b"nftmart-voucher", genesis_hash, voucher).encode()
+	}
+
+	/// The account holding the tokens of escrowed orders.
+	pub fn order_escrow_account() -> T::AccountId {
+		T::ModuleId::get().into_sub_account(*b"escrow")
+	}
+
+	/// Transfer a sold token from `seller` to `buyer`, out of escrow if the order of `seller` kept it there.
+	fn deliver_token(
+		escrowed: bool,
+		seller: &T::AccountId,
+		buyer: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		token_id: TokenIdOf<T>,
+		quantity: QuantityOf<T>,
+	) -> DispatchResult {
+		if escrowed {
+			orml_nft::Module::<T>::transfer(&Self::order_escrow_account(), buyer, (class_id, token_id), quantity)?;
+			Self::deposit_event(Event::SoldEscrowedToken(class_id, token_id, seller.clone(), buyer.clone(), quantity));
+			Ok(())
+		} else {
+			Self::do_transfer(seller, buyer, class_id, token_id, quantity)
+		}
+	}
+
+	/// The weight of counting the auctions ending at a block on creating an auction.
+	fn auction_end_scan_weight() -> Weight {
+		T::DbWeight::get().reads(T::MaxAuctionsEndingPerBlock::get() as Weight)
+	}
+
+	/// The weight of scanning the orders of a token on submitting an order: every order is read
+	/// by `best_match` along with the holding of an ask's owner, and counted again on creation.
+	fn order_scan_weight() -> Weight {
+		T::DbWeight::get().reads((T::MaxOrdersPerToken::get() as Weight).saturating_mul(3))
+	}
+
+	/// The best live order of another account on the other side of a new order of `who`, trading
+	/// the same quantity in the same currency: the lowest ask not above `price` for a bid, the
+	/// highest bid not below `price` for an ask.
+	fn best_match(
+		class_id: ClassIdOf<T>,
+		token_id: TokenIdOf<T>,
+		currency_id: CurrencyIdOf<T>,
+		price: Balance,
+		quantity: QuantityOf<T>,
+		by_token_owner: bool,
+		who: &T::AccountId,
+	) -> Option<(T::AccountId, OrderData<T>)> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let candidates = Orders::<T>::iter_prefix((class_id, token_id)).filter(|(owner, order)| {
+			owner != who &&
+			order.by_token_owner != by_token_owner &&
+			order.currency_id == currency_id &&
+			order.quantity == quantity &&
+			now <= order.deadline
+		});
+		if by_token_owner {
+			candidates
+				.filter(|(_, order)| order.price >= price)
+				.max_by_key(|(_, order)| order.price)
+		} else {
+			candidates
+				.filter(|(owner, order)| {
+					order.price <= price &&
+					(Self::escrowed_orders((class_id, token_id), owner).is_some() ||
+						orml_nft::Module::<T>::tokens_by_owner(owner, (class_id, token_id)) >= quantity)
+				})
+				.min_by_key(|(_, order)| order.price)
+		}
+	}
+
+	fn delete_order(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>, who: &T::AccountId) -> DispatchResult {
 		Orders::<T>::try_mutate_exists((class_id, token_id), who, |maybe_order| {
 			let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
 			let deposit = <T as Config>::Currency::unreserve(&who, order.deposit.saturated_into());
 			Self::deposit_event(Event::RemovedOrder(class_id, token_id, who.clone(), deposit.saturated_into()));
 
-			if who != token_owner {
+			// The token goes back to the seller, a taken order delivers it out of escrow beforehand.
+			if EscrowedOrders::<T>::take((class_id, token_id), who).is_some() {
+				orml_nft::Module::<T>::transfer(&Self::order_escrow_account(), who, (class_id, token_id), order.quantity)?;
+				Self::deposit_event(Event::ReleasedToken(class_id, token_id, who.clone(), order.quantity));
+			}
+
+			if !order.by_token_owner {
 				let _ = T::MultiCurrency::unreserve(order.currency_id, &who, order.price.saturated_into());
 			}
 
@@ -779,19 +3372,249 @@ impl<T: Config> Pallet<T> {
 				category.as_mut().map(|cate| cate.nft_count = cate.nft_count.saturating_sub(One::one()) );
 				Ok(())
 			})?;
+			OrderExpiry::<T>::remove(order.deadline, ExpiringOrder::Order((class_id, token_id), who.clone()));
+			OrdersByAccount::<T>::remove(who, (class_id, token_id));
+			OrdersByCategory::<T>::remove(order.category_id, ((class_id, token_id), who));
 
 			*maybe_order = None;
 			Ok(())
 		})
 	}
 
-	fn try_delete_order(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>, who: &T::AccountId, token_owner: &T::AccountId) {
-		let _ = Self::delete_order(class_id, token_id, who, token_owner);
+	fn delete_dutch_order(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>) -> DispatchResult {
+		let order: DutchOrderData<T> = DutchOrders::<T>::take((class_id, token_id)).ok_or(Error::<T>::OrderNotFound)?;
+		OrderExpiry::<T>::remove(order.end_block, ExpiringOrder::Dutch((class_id, token_id)));
+		let deposit = <T as Config>::Currency::unreserve(&order.owner, order.deposit.saturated_into());
+		Self::deposit_event(Event::RemovedDutchOrder(class_id, token_id, order.owner, deposit.saturated_into()));
+		Ok(())
+	}
+
+	/// Remove orders, dutch orders, bundle orders and collection offers whose deadline has passed,
+	/// at most `MaxExpiredOrdersPerBlock` in a block. The remaining ones will be removed in the following blocks.
+	fn remove_expired_orders(now: BlockNumberOf<T>) -> Weight {
+		let limit = T::MaxExpiredOrdersPerBlock::get();
+		let mut steps: u32 = 0;
+		let mut weight: Weight = 0;
+		let mut cursor = Self::expiry_cursor();
+		// Orders are valid until `deadline` inclusively.
+		while cursor < now && steps < limit {
+			let deadline = cursor;
+			let remaining = limit.saturating_sub(steps);
+			let expired: Vec<ExpiringOrderOf<T>> = OrderExpiry::<T>::iter_prefix(deadline)
+				.take(remaining as usize)
+				.map(|(key, _)| key)
+				.collect();
+			steps = steps.saturating_add(1);
+			if (expired.len() as u32) < remaining {
+				cursor = cursor.saturating_add(One::one());
+			}
+			for order in expired {
+				steps = steps.saturating_add(1);
+				weight = weight.saturating_add(Self::remove_expired_order(deadline, order));
+			}
+		}
+		ExpiryCursor::<T>::put(cursor);
+		T::DbWeight::get().reads_writes((steps as Weight).saturating_add(1), 1).saturating_add(weight)
+	}
+
+	/// Remove an expired order of `OrderExpiry`, returning the weight of the removal.
+	fn remove_expired_order(deadline: BlockNumberOf<T>, order: ExpiringOrderOf<T>) -> Weight {
+		let db = T::DbWeight::get();
+		let removed = match order.clone() {
+			ExpiringOrder::Order((class_id, token_id), who) =>
+				Self::delete_order(class_id, token_id, &who).map(|_| db.reads_writes(3, 8)),
+			ExpiringOrder::Dutch((class_id, token_id)) =>
+				Self::delete_dutch_order(class_id, token_id).map(|_| db.reads_writes(1, 3)),
+			ExpiringOrder::Bundle(order_id) => Self::delete_bundle_order(order_id)
+				.map(|_| db.reads_writes(1, (T::MaxBundleSize::get() as Weight).saturating_add(3))),
+			ExpiringOrder::Offer(class_id, offer_id) =>
+				Self::delete_offer(class_id, offer_id).map(|_| db.reads_writes(1, 4)),
+		};
+		removed.unwrap_or_else(|_| {
+			OrderExpiry::<T>::remove(deadline, order);
+			db.writes(1)
+		})
+	}
+
+	/// Remove a collection offer, unreserving the price of the remaining quantity and the deposit.
+	fn delete_offer(class_id: ClassIdOf<T>, offer_id: GlobalId) -> DispatchResult {
+		let offer: OfferData<T> = Offers::<T>::take(class_id, offer_id).ok_or(Error::<T>::OfferNotFound)?;
+		OrderExpiry::<T>::remove(offer.deadline, ExpiringOrder::Offer(class_id, offer_id));
+		let _ = T::MultiCurrency::unreserve(offer.currency_id, &offer.owner, offer.price.saturating_mul(offer.quantity as Balance));
+		let deposit = <T as Config>::Currency::unreserve(&offer.owner, offer.deposit.saturated_into());
+		Self::deposit_event(Event::RemovedOffer(class_id, offer_id, offer.owner, deposit.saturated_into()));
+		Ok(())
+	}
+
+	fn delete_bundle_order(order_id: GlobalId) -> DispatchResult {
+		let order: BundleOrderData<T> = BundleOrders::<T>::take(order_id).ok_or(Error::<T>::OrderNotFound)?;
+		for token in order.tokens.iter() {
+			TokenBundles::<T>::remove(token);
+		}
+		OrderExpiry::<T>::remove(order.deadline, ExpiringOrder::Bundle(order_id));
+		let deposit = <T as Config>::Currency::unreserve(&order.owner, order.deposit.saturated_into());
+		Self::deposit_event(Event::RemovedBundleOrder(order_id, order.owner, deposit.saturated_into()));
+		Ok(())
+	}
+
+	fn try_delete_order(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>, who: &T::AccountId) {
+		let _ = Self::delete_order(class_id, token_id, who);
+	}
+
+	/// The quantity of a token held by `who` which is not locked by `who`'s orders.
+	fn free_quantity(who: &T::AccountId, class_id: ClassIdOf<T>, token_id: TokenIdOf<T>) -> QuantityOf<T> {
+		let token = (class_id, token_id);
+		// A token listed for rent or rented is locked as a whole, an expired rental may not be removed yet.
+		let now = <frame_system::Pallet<T>>::block_number();
+		if Self::rental_listings(token).map_or(false, |listing| listing.owner == *who) ||
+			Self::rentals(token).map_or(false, |rental| rental.lender == *who && now < rental.end) {
+			return Zero::zero();
+		}
+		let mut locked: QuantityOf<T> = Zero::zero();
+		if let Some(order) = Self::orders(token, who) {
+			if order.by_token_owner && Self::escrowed_orders(token, who).is_none() {
+				locked = locked.saturating_add(order.quantity);
+			}
+		}
+		if Self::dutch_orders(token).map_or(false, |order| order.owner == *who) {
+			locked = locked.saturating_add(One::one());
+		}
+		if let Some(order_id) = Self::token_bundles(token) {
+			if Self::bundle_orders(order_id).map_or(false, |order| order.owner == *who) {
+				locked = locked.saturating_add(One::one());
+			}
+		}
+		orml_nft::Module::<T>::tokens_by_owner(who, token).saturating_sub(locked)
+	}
+
+	/// The account allowed to use a rented token, `None` if the token is not rented.
+	pub fn user_of(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>) -> Option<T::AccountId> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		Self::rentals((class_id, token_id)).filter(|rental| now < rental.end).map(|rental| rental.user)
+	}
+
+	/// Remove rentals which have ended, at most `MaxExpiredRentalsPerBlock` in a block.
+	/// The remaining ones will be removed in the following blocks.
+	fn remove_expired_rentals(now: BlockNumberOf<T>) -> Weight {
+		let limit = T::MaxExpiredRentalsPerBlock::get();
+		let mut steps: u32 = 0;
+		let mut removed: u32 = 0;
+		let mut cursor = Self::rental_expiry_cursor();
+		// Rentals expire at `end`.
+		while cursor <= now && steps < limit {
+			let end = cursor;
+			let remaining = limit.saturating_sub(steps);
+			let expired: Vec<(ClassIdOf<T>, TokenIdOf<T>)> = RentalExpiry::<T>::iter_prefix(end)
+				.take(remaining as usize)
+				.map(|(key, _)| key)
+				.collect();
+			steps = steps.saturating_add(1);
+			if (expired.len() as u32) < remaining {
+				cursor = cursor.saturating_add(One::one());
+			}
+			for token in expired {
+				steps = steps.saturating_add(1);
+				removed = removed.saturating_add(1);
+				RentalExpiry::<T>::remove(end, token);
+				if let Some(rental) = Rentals::<T>::take(token) {
+					Self::deposit_event(Event::RentalExpired(token.0, token.1, rental.user));
+				}
+			}
+		}
+		RentalExpiryCursor::<T>::put(cursor);
+		T::DbWeight::get().reads_writes(
+			(steps as Weight).saturating_add(removed as Weight).saturating_add(1),
+			(removed.saturating_mul(2) as Weight).saturating_add(1),
+		)
+	}
+
+	/// Check `currency_id` is accepted for tokens of `class_id` and `price` is neither zero nor below its floor.
+	fn ensure_currency_accepted(class_id: ClassIdOf<T>, currency_id: CurrencyIdOf<T>, price: Balance) -> DispatchResult {
+		let min_price = nftmart_config::Pallet::<T>::min_order_price(currency_id).ok_or(Error::<T>::CurrencyNotAccepted)?;
+		if let Some((currencies, _)) = Self::class_currencies(class_id) {
+			ensure!(currencies.contains(&currency_id), Error::<T>::CurrencyNotAccepted);
+		}
+		ensure!(price >= min_price.max(One::one()), Error::<T>::PriceBelowFloor);
+		Ok(())
+	}
+
+	/// `who` must be the owner of the class, and the metadata of the class must not be frozen.
+	fn ensure_attribute_editable(who: &T::AccountId, class_id: ClassIdOf<T>) -> DispatchResult {
+		let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+		ensure!(*who == class_info.owner, Error::<T>::NoPermission);
+		ensure!(!class_info.data.properties.0.contains(ClassProperty::MetadataFrozen), Error::<T>::MetadataFrozen);
+		Ok(())
+	}
+
+	/// Check the bounds of an attribute and return the deposit of it.
+	fn attribute_deposit(key: &[u8], value: &AttributeValue) -> Result<Balance, DispatchError> {
+		ensure!(key.len() <= T::MaxAttributeKeyLength::get() as usize, Error::<T>::AttributeKeyTooLong);
+		let value_len = value.encoded_size();
+		ensure!(value_len <= T::MaxAttributeValueLength::get() as usize, Error::<T>::AttributeValueTooLong);
+		Ok(((key.len() + value_len) as Balance).saturating_mul(T::MetaDataByteDeposit::get()))
+	}
+
+	/// Remove the attributes of a token once all of it has been burned.
+	fn remove_burned_token_attributes(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>) {
+		if orml_nft::Module::<T>::tokens(class_id, token_id).is_none() {
+			let owner: T::AccountId = T::ModuleId::get().into_sub_account(class_id);
+			for (_, (_, deposit)) in TokenAttributes::<T>::drain_prefix((class_id, token_id)) {
+				<T as Config>::Currency::unreserve(&owner, deposit.saturated_into());
+			}
+		}
+	}
+
+	/// `who` must be the owner of a revocable class, and `owner` must hold `quantity` of unlocked tokens.
+	/// The sell orders and the rental listing of `owner` on the token are removed first.
+	fn ensure_revocable(
+		who: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		token_id: TokenIdOf<T>,
+		owner: &T::AccountId,
+		quantity: QuantityOf<T>,
+	) -> DispatchResult {
+		let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+		ensure!(*who == class_info.owner, Error::<T>::NoPermission);
+		ensure!(class_info.data.properties.0.contains(ClassProperty::Revocable), Error::<T>::NonRevocable);
+		ensure!(orml_nft::Module::<T>::tokens(class_id, token_id).is_some(), Error::<T>::TokenIdNotFound);
+		ensure!(quantity >= One::one(), Error::<T>::InvalidQuantity);
+
+		Self::take_out_nested(owner, class_id, token_id)?;
+		ensure!(orml_nft::Module::<T>::tokens_by_owner(owner, (class_id, token_id)) >= quantity, Error::<T>::NoPermission);
+
+		let token = (class_id, token_id);
+		if Self::orders(token, owner).map_or(false, |order| order.by_token_owner) {
+			Self::delete_order(class_id, token_id, owner)?;
+		}
+		if Self::dutch_orders(token).map_or(false, |order| order.owner == *owner) {
+			Self::delete_dutch_order(class_id, token_id)?;
+		}
+		if Self::rental_listings(token).map_or(false, |listing| listing.owner == *owner) {
+			RentalListings::<T>::remove(token);
+			Self::deposit_event(Event::UnlistedRental(class_id, token_id));
+		}
+		ensure!(Self::free_quantity(owner, class_id, token_id) >= quantity, Error::<T>::OrderExists);
+		Ok(())
+	}
+
+	/// A nested token is moved out of its parent to `who` first, `who` must own the root token.
+	fn take_out_nested(who: &T::AccountId, class_id: ClassIdOf<T>, token_id: TokenIdOf<T>) -> DispatchResult {
+		if let Some(parent) = orml_nft::Module::<T>::parents((class_id, token_id)) {
+			orml_nft::Module::<T>::unnest(who, (class_id, token_id))?;
+			Self::deposit_event(Event::Unnested(class_id, token_id, parent.0, parent.1));
+		}
+		Ok(())
 	}
 
 	/// Ensured atomic.
 	#[transactional]
-	fn do_transfer(from: &T::AccountId, to: &T::AccountId, class_id: ClassIdOf<T>, token_id: TokenIdOf<T>) -> DispatchResult {
+	fn do_transfer(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		token_id: TokenIdOf<T>,
+		quantity: QuantityOf<T>,
+	) -> DispatchResult {
 		let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
 		let data = class_info.data;
 		ensure!(
@@ -799,24 +3622,73 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::NonTransferable
 		);
 
-		let token_info = orml_nft::Module::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?;
-		ensure!(*from == token_info.owner, Error::<T>::NoPermission);
-
-		ensure!(Self::orders((class_id, token_id), from).is_none(), Error::<T>::OrderExists);
+		ensure!(orml_nft::Module::<T>::tokens(class_id, token_id).is_some(), Error::<T>::TokenIdNotFound);
+		Self::take_out_nested(from, class_id, token_id)?;
+		ensure!(orml_nft::Module::<T>::tokens_by_owner(from, (class_id, token_id)) >= quantity, Error::<T>::NoPermission);
+		ensure!(Self::free_quantity(from, class_id, token_id) >= quantity, Error::<T>::OrderExists);
 
-		orml_nft::Module::<T>::transfer(from, to, (class_id, token_id))?;
+		orml_nft::Module::<T>::transfer(from, to, (class_id, token_id), quantity)?;
 
-		Self::deposit_event(Event::TransferredToken(from.clone(), to.clone(), class_id, token_id));
+		Self::deposit_event(Event::TransferredToken(from.clone(), to.clone(), class_id, token_id, quantity));
 		Ok(())
 	}
 
+	/// Mint `quantity` of a new token to `to`, the deposit is reserved from the class owner.
+	fn do_mint(
+		to: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		class_info: &orml_nft::ClassInfoOf<T>,
+		metadata: NFTMetadata,
+		quantity: QuantityOf<T>,
+	) -> Result<TokenIdOf<T>, DispatchError> {
+		MintedByClass::<T>::try_mutate(class_id, |minted| -> DispatchResult {
+			*minted = minted.saturating_add(quantity);
+			if let Some(max_supply) = class_info.data.max_supply {
+				ensure!(*minted <= max_supply, Error::<T>::ExceedMaxSupply);
+			}
+			Ok(())
+		})?;
+		MintedByAccount::<T>::try_mutate(class_id, to, |minted| -> DispatchResult {
+			*minted = minted.saturating_add(quantity);
+			if let Some(max_mint) = class_info.data.max_mint_per_account {
+				ensure!(*minted <= max_mint, Error::<T>::ExceedMaxMintPerAccount);
+			}
+			Ok(())
+		})?;
+		let (deposit, total_deposit) = Self::mint_token_deposit(metadata.len().saturated_into(), quantity);
+
+		<T as Config>::Currency::reserve(&class_info.owner, total_deposit.saturated_into())?;
+		let data: TokenData<BlockNumberOf<T>> = TokenData {
+			deposit,
+			create_block: <frame_system::Pallet<T>>::block_number(),
+		};
+		let token_id = orml_nft::Module::<T>::mint(to, class_id, metadata, data, quantity)?;
+
+		Self::deposit_event(Event::MintedToken(class_info.owner.clone(), to.clone(), class_id, token_id, quantity));
+		Ok(token_id)
+	}
+
+	/// Check `who` is a leaf of the Merkle tree with `root`, a leaf is the hash of the encoded account.
+	/// Pairs are hashed in sorted order, so the proof does not tell the position of the leaf.
+	pub fn verify_allowlist(root: H256, who: &T::AccountId, proof: &[H256]) -> bool {
+		let leaf = BlakeTwo256::hash_of(who);
+		let computed = proof.iter().fold(leaf, |hash, node| {
+			if hash <= *node {
+				BlakeTwo256::hash_of(&(hash, *node))
+			} else {
+				BlakeTwo256::hash_of(&(*node, hash))
+			}
+		});
+		computed == root
+	}
+
 	pub fn add_class_admin_deposit(admin_count: u32) -> Balance {
 		let proxy_deposit_before: Balance = <pallet_proxy::Module<T>>::deposit(1).saturated_into();
 		let proxy_deposit_after: Balance = <pallet_proxy::Module<T>>::deposit(admin_count.saturating_add(1)).saturated_into();
 		proxy_deposit_after.saturating_sub(proxy_deposit_before)
 	}
 
-	pub fn mint_token_deposit(metadata_len: u32, quantity: u32) -> (Balance, Balance) {
+	pub fn mint_token_deposit(metadata_len: u32, quantity: QuantityOf<T>) -> (Balance, Balance) {
 		let deposit: Balance = {
 			let total_bytes = metadata_len;
 			T::CreateTokenDeposit::get().saturating_add(
@@ -837,4 +3709,166 @@ impl<T: Config> Pallet<T> {
 		let proxy_deposit: Balance = <pallet_proxy::Module<T>>::deposit(1).saturated_into();
 		(deposit, deposit.saturating_add(proxy_deposit))
 	}
+
+	/// The tokens held by `who` as `(class_id, token_id, quantity)`, `page_size` entries from `page`.
+	pub fn tokens_of(who: &T::AccountId, page: u32, page_size: u32) -> Vec<(ClassIdOf<T>, TokenIdOf<T>, QuantityOf<T>)> {
+		orml_nft::TokensByOwner::<T>::iter_prefix(who)
+			.skip((page as usize).saturating_mul(page_size as usize))
+			.take(page_size as usize)
+			.map(|((class_id, token_id), quantity)| (class_id, token_id, quantity))
+			.collect()
+	}
+
+	pub fn class_info(class_id: ClassIdOf<T>) -> Option<orml_nft::ClassInfoOf<T>> {
+		orml_nft::Module::<T>::classes(class_id)
+	}
+
+	pub fn token_info(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>) -> Option<orml_nft::TokenInfoOf<T>> {
+		orml_nft::Module::<T>::tokens(class_id, token_id)
+	}
+
+	fn order_info(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>, owner: T::AccountId, order: OrderData<T>) -> OrderInfoOf<T> {
+		OrderInfo {
+			class_id,
+			token_id,
+			owner,
+			currency_id: order.currency_id,
+			price: order.price,
+			deposit: order.deposit,
+			deadline: order.deadline,
+			category_id: order.category_id,
+			quantity: order.quantity,
+			by_token_owner: order.by_token_owner,
+		}
+	}
+
+	/// The orders, sells and bids, on a token, `page_size` entries from `page`.
+	pub fn orders_of_token(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>, page: u32, page_size: u32) -> Vec<OrderInfoOf<T>> {
+		Orders::<T>::iter_prefix((class_id, token_id))
+			.skip((page as usize).saturating_mul(page_size as usize))
+			.take(page_size as usize)
+			.map(|(owner, order)| Self::order_info(class_id, token_id, owner, order))
+			.collect()
+	}
+
+	/// The orders placed by `who`, `page_size` entries from `page`.
+	pub fn orders_by_account(who: &T::AccountId, page: u32, page_size: u32) -> Vec<OrderInfoOf<T>> {
+		OrdersByAccount::<T>::iter_prefix(who)
+			.skip((page as usize).saturating_mul(page_size as usize))
+			.take(page_size as usize)
+			.filter_map(|((class_id, token_id), _)| {
+				Self::orders((class_id, token_id), who).map(|order| Self::order_info(class_id, token_id, who.clone(), order))
+			})
+			.collect()
+	}
+
+	/// The orders in `category_id`, `page_size` entries from `page`. Their number is `nft_count`.
+	pub fn orders_of_category(category_id: CategoryIdOf<T>, page: u32, page_size: u32) -> Vec<OrderInfoOf<T>> {
+		OrdersByCategory::<T>::iter_prefix(category_id)
+			.skip((page as usize).saturating_mul(page_size as usize))
+			.take(page_size as usize)
+			.filter_map(|(((class_id, token_id), owner), _)| {
+				Self::orders((class_id, token_id), &owner).map(|order| Self::order_info(class_id, token_id, owner, order))
+			})
+			.collect()
+	}
+
+	/// The categories, `page_size` entries from `page`.
+	pub fn all_categories(page: u32, page_size: u32) -> Vec<(CategoryIdOf<T>, CategoryData)> {
+		Categories::<T>::iter()
+			.skip((page as usize).saturating_mul(page_size as usize))
+			.take(page_size as usize)
+			.collect()
+	}
+}
+
+impl<T: Config> Auction<T::AccountId, BlockNumberOf<T>> for Pallet<T> {
+	type AuctionId = GlobalId;
+	type Balance = Balance;
+
+	fn auction_info(id: Self::AuctionId) -> Option<AuctionInfo<T::AccountId, Self::Balance, BlockNumberOf<T>>> {
+		Self::auctions(id)
+	}
+
+	fn update_auction(id: Self::AuctionId, info: AuctionInfo<T::AccountId, Self::Balance, BlockNumberOf<T>>) -> DispatchResult {
+		let auction = Auctions::<T>::get(id).ok_or(Error::<T>::AuctionNotFound)?;
+		if let Some(new_end) = info.end.filter(|end| auction.end != Some(*end)) {
+			Self::ensure_auction_end_available(new_end)?;
+		}
+		if let Some(old_end) = auction.end {
+			AuctionEndTime::<T>::remove(&old_end, id);
+		}
+		if let Some(new_end) = info.end {
+			AuctionEndTime::<T>::insert(&new_end, id, ());
+		}
+		Auctions::<T>::insert(id, info);
+		Ok(())
+	}
+
+	fn new_auction(start: BlockNumberOf<T>, end: Option<BlockNumberOf<T>>) -> Result<Self::AuctionId, DispatchError> {
+		if let Some(end_block) = end {
+			Self::ensure_auction_end_available(end_block)?;
+		}
+		let auction = AuctionInfo { bid: None, start, end };
+		let auction_id = Self::fetch_id()?;
+		Auctions::<T>::insert(auction_id, auction);
+		if let Some(end_block) = end {
+			AuctionEndTime::<T>::insert(&end_block, auction_id, ());
+		}
+		Ok(auction_id)
+	}
+
+	fn remove_auction(id: Self::AuctionId) {
+		if let Some(auction) = Auctions::<T>::take(&id) {
+			if let Some(end_block) = auction.end {
+				AuctionEndTime::<T>::remove(end_block, id);
+			}
+		}
+	}
+}
+
+impl<T: Config> AuctionHandler<T::AccountId, Balance, BlockNumberOf<T>, GlobalId> for Pallet<T> {
+	fn on_new_bid(
+		_now: BlockNumberOf<T>,
+		id: GlobalId,
+		new_bid: (T::AccountId, Balance),
+		last_bid: Option<(T::AccountId, Balance)>,
+	) -> OnNewBidResult<BlockNumberOf<T>> {
+		let accept_bid = Self::auction_items(id).map_or(false, |item| {
+			let min_price = match last_bid {
+				Some((_, last_price)) => last_price.saturating_add(item.min_raise),
+				None => item.reserve_price,
+			};
+			new_bid.0 != item.owner &&
+				new_bid.1 >= min_price &&
+				T::MultiCurrency::reserve(item.currency_id, &new_bid.0, new_bid.1).is_ok()
+		});
+
+		if accept_bid {
+			// refund the previous bidder.
+			if let (Some(item), Some((last_bidder, last_price))) = (Self::auction_items(id), last_bid) {
+				let _ = T::MultiCurrency::unreserve(item.currency_id, &last_bidder, last_price);
+			}
+		}
+
+		OnNewBidResult {
+			accept_bid,
+			auction_end_change: Change::NoChange,
+		}
+	}
+
+	fn on_auction_ended(id: GlobalId, winner: Option<(T::AccountId, Balance)>) {
+		if let Some(item) = AuctionItems::<T>::take(id) {
+			<T as Config>::Currency::unreserve(&item.owner, item.deposit.saturated_into());
+			if let Some((bidder, price)) = winner {
+				if Self::settle_auction(&item, &bidder, price).is_ok() {
+					Self::deposit_event(Event::AuctionSettled(id, bidder, price));
+					return;
+				}
+				let _ = T::MultiCurrency::unreserve(item.currency_id, &bidder, price);
+			}
+			let _ = orml_nft::Module::<T>::transfer(&Self::escrow_account(), &item.owner, (item.class_id, item.token_id), One::one());
+			Self::deposit_event(Event::AuctionEndedWithoutBid(id, item.owner));
+		}
+	}
 }
@@ -12,9 +12,12 @@ use frame_support::{
 	RuntimeDebug,
 };
 use sp_core::{crypto::AccountId32, H256};
+use sp_keystore::{testing::KeyStore, KeystoreExt};
+use std::sync::Arc;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	MultiSignature, MultiSigner,
 };
 
 parameter_types! {
@@ -77,6 +80,9 @@ pub enum ProxyType {
 	Any,
 	JustTransfer,
 	JustUtility,
+	ClassAdmin,
+	ClassMinter,
+	ClassMetadataEditor,
 }
 impl Default for ProxyType {
 	fn default() -> Self {
@@ -89,10 +95,50 @@ impl InstanceFilter<Call> for ProxyType {
 			ProxyType::Any => true,
 			ProxyType::JustTransfer => matches!(c, Call::Balances(pallet_balances::Call::transfer(..))),
 			ProxyType::JustUtility => matches!(c, Call::Utility(..)),
+			ProxyType::ClassAdmin => matches!(
+				c,
+				Call::Nftmart(nftmart_nft::Call::mint(..)) |
+				Call::Nftmart(nftmart_nft::Call::update_class(..)) |
+				Call::Nftmart(nftmart_nft::Call::update_token_metadata(..)) |
+				Call::Nftmart(nftmart_nft::Call::freeze_metadata(..)) |
+				Call::Nftmart(nftmart_nft::Call::set_class_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::clear_class_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::set_token_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::clear_token_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::add_class_admin(..)) |
+				Call::Nftmart(nftmart_nft::Call::remove_class_admin(..)) |
+				Call::Nftmart(nftmart_nft::Call::set_drop(..)) |
+				Call::Nftmart(nftmart_nft::Call::remove_drop(..)) |
+				Call::Nftmart(nftmart_nft::Call::set_class_currencies(..)) |
+				Call::Nftmart(nftmart_nft::Call::revoke(..)) |
+				Call::Nftmart(nftmart_nft::Call::reassign(..))
+			),
+			ProxyType::ClassMinter => matches!(c, Call::Nftmart(nftmart_nft::Call::mint(..))),
+			ProxyType::ClassMetadataEditor => matches!(
+				c,
+				Call::Nftmart(nftmart_nft::Call::update_class(..)) |
+				Call::Nftmart(nftmart_nft::Call::update_token_metadata(..)) |
+				Call::Nftmart(nftmart_nft::Call::freeze_metadata(..)) |
+				Call::Nftmart(nftmart_nft::Call::set_class_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::clear_class_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::set_token_attribute(..)) |
+				Call::Nftmart(nftmart_nft::Call::clear_token_attribute(..))
+			),
 		}
 	}
 	fn is_superset(&self, o: &Self) -> bool {
-		self == &ProxyType::Any || self == o
+		self == &ProxyType::Any || self == o ||
+			(self == &ProxyType::ClassAdmin && matches!(o, ProxyType::ClassMinter | ProxyType::ClassMetadataEditor))
+	}
+}
+pub struct ClassAdminProxy;
+impl sp_runtime::traits::Convert<ClassAdminRole, ProxyType> for ClassAdminProxy {
+	fn convert(role: ClassAdminRole) -> ProxyType {
+		match role {
+			ClassAdminRole::Admin => ProxyType::ClassAdmin,
+			ClassAdminRole::Minter => ProxyType::ClassMinter,
+			ClassAdminRole::MetadataEditor => ProxyType::ClassMetadataEditor,
+		}
 	}
 }
 pub struct BaseFilter;
@@ -139,10 +185,12 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = ();
+	type FirstCreatedCurrencyId = FirstCreatedCurrencyId;
 }
 
 parameter_types! {
 	pub const GetNativeCurrencyId: sp_core::constants_types::CurrencyId = sp_core::constants_types::NATIVE_CURRENCY_ID;
+	pub const FirstCreatedCurrencyId: sp_core::constants_types::CurrencyId = 1000;
 }
 
 pub type AdaptedBasicCurrency = BasicCurrencyAdapter<Runtime, Balances, sp_core::constants_types::Amount, sp_core::constants_types::Moment>;
@@ -155,11 +203,18 @@ impl orml_currencies::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const MaxNestingDepth: u32 = 3;
+}
+
 impl orml_nft::Config for Runtime {
 	type ClassId = sp_core::constants_types::ClassId;
 	type TokenId = sp_core::constants_types::TokenId;
+	type Quantity = sp_core::constants_types::Quantity;
 	type ClassData = nftmart_nft::ClassData<BlockNumberOf<Self>>;
 	type TokenData = nftmart_nft::TokenData<BlockNumberOf<Self>>;
+	type TokenAccount = nftmart_nft::TokenAccount<Self>;
+	type MaxNestingDepth = MaxNestingDepth;
 }
 
 parameter_types! {
@@ -167,6 +222,18 @@ parameter_types! {
 	pub const CreateTokenDeposit: Balance = 10;
 	pub const MetaDataByteDeposit: Balance = 1;
 	pub const NftModuleId: ModuleId = ModuleId(*b"nftmart*");
+	pub const PlatformFeeAccount: AccountId = FEE_ACCOUNT;
+	pub const MaxRoyaltyRate: Perbill = Perbill::from_percent(50);
+	pub const MaxExpiredOrdersPerBlock: u32 = 3;
+	pub const MaxExpiredRentalsPerBlock: u32 = 3;
+	pub const MaxAuctionsEndingPerBlock: u32 = 2;
+	pub const MaxBundleSize: u32 = 3;
+	pub const MaxOrdersPerToken: u32 = 2;
+	pub const MaxAttributeKeyLength: u32 = 8;
+	pub const MaxAttributeValueLength: u32 = 16;
+	pub const MaxClassCurrencies: u32 = 2;
+	pub const MaxProofLength: u32 = 4;
+	pub const MaxDropMetadataLength: u32 = 16;
 }
 
 impl nftmart_nft::Config for Runtime {
@@ -178,10 +245,30 @@ impl nftmart_nft::Config for Runtime {
 	type Currency = Balances;
 	type MultiCurrency = Currencies;
 	type CategoryId = sp_core::constants_types::CategoryId;
+	type PlatformFeeAccount = PlatformFeeAccount;
+	type MaxRoyaltyRate = MaxRoyaltyRate;
+	type MaxExpiredOrdersPerBlock = MaxExpiredOrdersPerBlock;
+	type MaxExpiredRentalsPerBlock = MaxExpiredRentalsPerBlock;
+	type MaxAuctionsEndingPerBlock = MaxAuctionsEndingPerBlock;
+	type MaxBundleSize = MaxBundleSize;
+	type MaxOrdersPerToken = MaxOrdersPerToken;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type MaxClassCurrencies = MaxClassCurrencies;
+	type MaxProofLength = MaxProofLength;
+	type MaxDropMetadataLength = MaxDropMetadataLength;
+	type ClassAdminProxy = ClassAdminProxy;
+	type OffchainSignature = MultiSignature;
+	type OffchainPublic = MultiSigner;
+	type CurrencyFactory = Currencies;
+	type WeightInfo = ();
 }
 
 impl nftmart_config::Config for Runtime {
 	type Event = Event;
+	type CurrencyId = CurrencyId;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type WeightInfo = ();
 }
 
 use frame_system::Call as SystemCall;
@@ -209,6 +296,7 @@ construct_runtime!(
 
 pub const ALICE: AccountId = AccountId::new([1u8; 32]);
 pub const BOB: AccountId = AccountId::new([2u8; 32]);
+pub const FEE_ACCOUNT: AccountId = AccountId::new([9u8; 32]);
 pub const CLASS_ID: <Runtime as orml_nft::Config>::ClassId = 0;
 pub const CATEGORY_ID: <Runtime as Config>::CategoryId = 0;
 pub const CATEGORY_ID_NOT_EXIST: <Runtime as Config>::CategoryId = 100;
@@ -236,6 +324,8 @@ impl ExtBuilder {
 		.unwrap();
 
 		let mut ext = sp_io::TestExternalities::new(t);
+		// Mint vouchers are signed with the keystore in benchmarks.
+		ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
 		ext.execute_with(|| {
 			System::set_block_number(1);
 			NftmartConfig::add_whitelist(Origin::root(), ALICE);
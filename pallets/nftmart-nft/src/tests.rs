@@ -1,8 +1,9 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
 use mock::{Event, *};
+use sp_core::constants_types::NATIVE_CURRENCY_ID;
 
 fn free_balance(who: &AccountId) -> Balance {
 	<Runtime as Config>::Currency::free_balance(who)
@@ -59,7 +60,7 @@ fn create_class_should_work() {
 		let metadata = vec![1];
 		let name = vec![1];
 		let description = vec![1];
-		assert_ok!(Nftmart::create_class(Origin::signed(ALICE), metadata.clone(), name.clone(), description.clone(), Default::default()));
+		assert_ok!(Nftmart::create_class(Origin::signed(ALICE), metadata.clone(), name.clone(), description.clone(), Default::default(), Perbill::zero(), None, None));
 
 		let event = Event::nftmart_nft(crate::Event::CreatedClass(class_id_account(), CLASS_ID));
 		assert_eq!(last_event(), event);
@@ -76,7 +77,9 @@ fn create_class_should_fail() {
 			Nftmart::create_class(
 				Origin::signed(BOB),
 				vec![1], vec![1], vec![1],
-				Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+				Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+				Perbill::zero(),
+				None, None
 			),
 			pallet_balances::Error::<Runtime, _>::InsufficientBalance
 		);
@@ -93,7 +96,9 @@ fn mint_should_work() {
 			assert_ok!(Nftmart::create_class(
 				Origin::signed(ALICE),
 				metadata.clone(), name.clone(), description.clone(),
-				Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+				Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+				Perbill::zero(),
+				None, None
 			));
 			let event = Event::nftmart_nft(crate::Event::CreatedClass(class_id_account(), CLASS_ID));
 			assert_eq!(last_event(), event);
@@ -116,8 +121,10 @@ fn mint_should_work() {
 			vec![1],
 			count as u32
 		));
-		let event = Event::nftmart_nft(crate::Event::MintedToken(class_id_account(), BOB, CLASS_ID, count as u32));
+		let event = Event::nftmart_nft(crate::Event::MintedToken(class_id_account(), BOB, CLASS_ID, TOKEN_ID, count as u32));
 		assert_eq!(last_event(), event);
+		assert_eq!(OrmlNFT::tokens(CLASS_ID, TOKEN_ID).unwrap().quantity, count as u32);
+		assert_eq!(OrmlNFT::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID)), count as u32);
 
 		assert_eq!(reserved_balance(&class_id_account()), reserved);
 	});
@@ -130,7 +137,9 @@ fn mint_should_fail() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata.clone(), vec![1], vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+			Perbill::zero(),
+			None, None
 		));
 		assert_noop!(
 			Nftmart::mint(Origin::signed(ALICE), BOB, CLASS_ID_NOT_EXIST, vec![1], 2),
@@ -168,7 +177,9 @@ fn transfer_should_work() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata.clone(), vec![1], vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+			Perbill::zero(),
+			None, None
 		));
 		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
@@ -180,12 +191,12 @@ fn transfer_should_work() {
 			2
 		));
 
-		assert_ok!(Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID));
-		let event = Event::nftmart_nft(crate::Event::TransferredToken(BOB, ALICE, CLASS_ID, TOKEN_ID));
+		assert_ok!(Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID, 1));
+		let event = Event::nftmart_nft(crate::Event::TransferredToken(BOB, ALICE, CLASS_ID, TOKEN_ID, 1));
 		assert_eq!(last_event(), event);
 
-		assert_ok!(Nftmart::transfer(Origin::signed(ALICE), BOB, CLASS_ID, TOKEN_ID));
-		let event = Event::nftmart_nft(crate::Event::TransferredToken(ALICE, BOB, CLASS_ID, TOKEN_ID));
+		assert_ok!(Nftmart::transfer(Origin::signed(ALICE), BOB, CLASS_ID, TOKEN_ID, 1));
+		let event = Event::nftmart_nft(crate::Event::TransferredToken(ALICE, BOB, CLASS_ID, TOKEN_ID, 1));
 		assert_eq!(last_event(), event);
 	});
 }
@@ -198,7 +209,9 @@ fn transfer_should_fail() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata.clone(), vec![1], vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+			Perbill::zero(),
+			None, None
 		));
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
 		assert_ok!(Nftmart::mint(
@@ -209,15 +222,15 @@ fn transfer_should_fail() {
 			1
 		));
 		assert_noop!(
-			Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID_NOT_EXIST, TOKEN_ID),
+			Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID_NOT_EXIST, TOKEN_ID, 1),
 			Error::<Runtime>::ClassIdNotFound
 		);
 		assert_noop!(
-			Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID_NOT_EXIST),
+			Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID_NOT_EXIST, 1),
 			Error::<Runtime>::TokenIdNotFound
 		);
 		assert_noop!(
-			Nftmart::transfer(Origin::signed(ALICE), BOB, CLASS_ID, TOKEN_ID),
+			Nftmart::transfer(Origin::signed(ALICE), BOB, CLASS_ID, TOKEN_ID, 1),
 			Error::<Runtime>::NoPermission
 		);
 	});
@@ -226,7 +239,9 @@ fn transfer_should_fail() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata.clone(), vec![1], vec![1],
-			Default::default()
+			Default::default(),
+			Perbill::zero(),
+			None, None
 		));
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
 		assert_ok!(Nftmart::mint(
@@ -237,7 +252,7 @@ fn transfer_should_fail() {
 			1
 		));
 		assert_noop!(
-			Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID),
+			Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID, 1),
 			Error::<Runtime>::NonTransferable
 		);
 	});
@@ -254,7 +269,9 @@ fn burn_should_work() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata, name, description,
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+			Perbill::zero(),
+			None, None
 		));
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
 		assert_ok!(Nftmart::mint(
@@ -268,8 +285,8 @@ fn burn_should_work() {
 			reserved_balance(&class_id_account()),
 			deposit_class.saturating_add(deposit_token)
 		);
-		assert_ok!(Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
-		let event = Event::nftmart_nft(crate::Event::BurnedToken(BOB, CLASS_ID, TOKEN_ID));
+		assert_ok!(Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 1));
+		let event = Event::nftmart_nft(crate::Event::BurnedToken(BOB, CLASS_ID, TOKEN_ID, 1));
 		assert_eq!(last_event(), event);
 
 		assert_eq!(
@@ -289,7 +306,9 @@ fn burn_should_fail() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata.clone(), name.clone(), description.clone(),
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+			Perbill::zero(),
+			None, None
 		));
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
 		assert_ok!(Nftmart::mint(
@@ -300,12 +319,12 @@ fn burn_should_fail() {
 			1
 		));
 		assert_noop!(
-			Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID_NOT_EXIST),
+			Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID_NOT_EXIST, 1),
 			Error::<Runtime>::TokenIdNotFound
 		);
 
 		assert_noop!(
-			Nftmart::burn(Origin::signed(ALICE), CLASS_ID, TOKEN_ID),
+			Nftmart::burn(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 1),
 			Error::<Runtime>::NoPermission
 		);
 
@@ -313,7 +332,7 @@ fn burn_should_fail() {
 			class_info.as_mut().unwrap().total_issuance = 0;
 		});
 		assert_noop!(
-			Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID),
+			Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 1),
 			orml_nft::Error::<Runtime>::NumOverflow
 		);
 	});
@@ -322,7 +341,9 @@ fn burn_should_fail() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata.clone(), name.clone(), description.clone(),
-			Default::default()
+			Default::default(),
+			Perbill::zero(),
+			None, None
 		));
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
 		assert_ok!(Nftmart::mint(
@@ -333,7 +354,7 @@ fn burn_should_fail() {
 			1
 		));
 		assert_noop!(
-			Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID),
+			Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 1),
 			Error::<Runtime>::NonBurnable
 		);
 	});
@@ -354,7 +375,9 @@ fn destroy_class_should_work() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata, name, description,
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+			Perbill::zero(),
+			None, None
 		));
 		assert_eq!(free_balance(&ALICE), 100000 - deposit_class);
 		assert_eq!(free_balance(&class_id_account()), 0);
@@ -369,7 +392,7 @@ fn destroy_class_should_work() {
 		));
 		assert_eq!(free_balance(&class_id_account()), 0);
 		assert_eq!(reserved_balance(&class_id_account()), deposit_class.saturating_add(deposit_token));
-		assert_ok!(Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
+		assert_ok!(Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 1));
 		assert_eq!(reserved_balance(&class_id_account()), deposit_class);
 		assert_eq!(free_balance(&class_id_account()), 0);
 		assert_ok!(Nftmart::destroy_class(
@@ -399,7 +422,9 @@ fn destroy_class_should_fail() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata, name, description,
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+			Perbill::zero(),
+			None, None
 		));
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
 		assert_ok!(Nftmart::mint(
@@ -424,7 +449,7 @@ fn destroy_class_should_fail() {
 			Error::<Runtime>::CannotDestroyClass
 		);
 
-		assert_ok!(Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
+		assert_ok!(Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 1));
 		assert_ok!(Nftmart::destroy_class(
 			Origin::signed(class_id_account()),
 			CLASS_ID,
@@ -432,3 +457,1523 @@ fn destroy_class_should_fail() {
 		));
 	});
 }
+
+/// Create a class and mint `count` tokens of quantity one to `owner`.
+fn add_class_and_mint(owner: AccountId, count: u32) {
+	let metadata = vec![1];
+	assert_ok!(Nftmart::create_class(
+		Origin::signed(ALICE),
+		metadata.clone(), vec![1], vec![1],
+		Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+		Perbill::zero(),
+		None, None
+	));
+	let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, count).1;
+	assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
+	for _ in 0..count {
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), owner.clone(), CLASS_ID, metadata.clone(), 1));
+	}
+}
+
+#[test]
+fn auction_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+		let _ = Balances::deposit_creating(&BOB, 100);
+		assert_ok!(Nftmart::create_auction(Origin::signed(BOB), NATIVE_CURRENCY_ID, CLASS_ID, TOKEN_ID, 50, 10, 0, 1, 10));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::CreatedAuction(0, CLASS_ID, TOKEN_ID, BOB)));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&Nftmart::escrow_account(), (CLASS_ID, TOKEN_ID)));
+
+		assert_noop!(Nftmart::bid(Origin::signed(ALICE), 0, 40), Error::<Runtime>::BidNotAccepted);
+		assert_noop!(Nftmart::bid(Origin::signed(BOB), 0, 60), Error::<Runtime>::BidNotAccepted);
+		assert_ok!(Nftmart::bid(Origin::signed(ALICE), 0, 50));
+		assert_eq!(reserved_balance(&ALICE), 50);
+		assert_noop!(Nftmart::bid(Origin::signed(ALICE), 0, 55), Error::<Runtime>::BidNotAccepted);
+		assert_ok!(Nftmart::bid(Origin::signed(ALICE), 0, 60));
+		assert_eq!(reserved_balance(&ALICE), 60);
+		assert_noop!(Nftmart::cancel_auction(Origin::signed(BOB), 0), Error::<Runtime>::AuctionHasBids);
+
+		System::set_block_number(10);
+		Nftmart::on_initialize(10);
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::AuctionSettled(0, ALICE, 60)));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(reserved_balance(&ALICE), 0);
+		assert_eq!(free_balance(&BOB), 160);
+		assert_eq!(Nftmart::auctions(0), None);
+		assert_eq!(Nftmart::auction_items(0), None);
+	});
+}
+
+#[test]
+fn auction_without_bid_should_return_nft() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+		assert_ok!(Nftmart::create_auction(Origin::signed(BOB), NATIVE_CURRENCY_ID, CLASS_ID, TOKEN_ID, 50, 10, 0, 1, 10));
+		assert_noop!(Nftmart::cancel_auction(Origin::signed(ALICE), 0), Error::<Runtime>::NoPermission);
+
+		System::set_block_number(10);
+		Nftmart::on_initialize(10);
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::AuctionEndedWithoutBid(0, BOB)));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&BOB, (CLASS_ID, TOKEN_ID)));
+		assert_noop!(Nftmart::bid(Origin::signed(ALICE), 0, 50), Error::<Runtime>::AuctionNotFound);
+	});
+}
+
+#[test]
+fn auctions_ending_per_block_should_be_bounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 3);
+		assert_ok!(Nftmart::create_auction(Origin::signed(BOB), NATIVE_CURRENCY_ID, CLASS_ID, TOKEN_ID, 50, 10, 0, 1, 10));
+		assert_ok!(Nftmart::create_auction(Origin::signed(BOB), NATIVE_CURRENCY_ID, CLASS_ID, TOKEN_ID + 1, 50, 10, 0, 1, 10));
+		assert_noop!(
+			Nftmart::create_auction(Origin::signed(BOB), NATIVE_CURRENCY_ID, CLASS_ID, TOKEN_ID + 2, 50, 10, 0, 1, 10),
+			Error::<Runtime>::TooManyEndingAuctions
+		);
+		assert_ok!(Nftmart::create_auction(Origin::signed(BOB), NATIVE_CURRENCY_ID, CLASS_ID, TOKEN_ID + 2, 50, 10, 0, 1, 11));
+
+		assert_ok!(Nftmart::cancel_auction(Origin::signed(BOB), 0));
+		assert_ok!(Nftmart::create_auction(Origin::signed(BOB), NATIVE_CURRENCY_ID, CLASS_ID, TOKEN_ID, 50, 10, 0, 1, 10));
+	});
+}
+
+#[test]
+fn dutch_order_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_noop!(
+			Nftmart::submit_dutch_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, CATEGORY_ID, CLASS_ID, TOKEN_ID, 100, 0, 0, 1, 11, 1),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			Nftmart::submit_dutch_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, CATEGORY_ID, CLASS_ID, TOKEN_ID, 100, 200, 0, 1, 11, 1),
+			Error::<Runtime>::InvalidDutchPrice
+		);
+		assert_noop!(
+			Nftmart::submit_dutch_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, CATEGORY_ID, CLASS_ID, TOKEN_ID, 100, 0, 0, 1, 11, 1),
+			Error::<Runtime>::PriceBelowFloor
+		);
+		assert_ok!(Nftmart::submit_dutch_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, CATEGORY_ID, CLASS_ID, TOKEN_ID, 100, 10, 0, 1, 11, 1));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::CreatedDutchOrder(CLASS_ID, TOKEN_ID, BOB)));
+		assert_eq!(Nftmart::categories(CATEGORY_ID).unwrap().nft_count, 0);
+		assert_noop!(Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID, 1), Error::<Runtime>::OrderExists);
+
+		let order = Nftmart::dutch_orders((CLASS_ID, TOKEN_ID)).unwrap();
+		assert_eq!(order.price_at(1), 100);
+		assert_eq!(order.price_at(6), 55);
+		assert_eq!(order.price_at(20), 10);
+		assert_eq!(DutchOrderData::<Runtime> { step: 4, ..order }.price_at(6), 64);
+
+		System::set_block_number(6);
+		assert_noop!(Nftmart::take_dutch_order(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 54), Error::<Runtime>::CanNotAfford);
+		assert_ok!(Nftmart::take_dutch_order(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 55));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::TakenDutchOrder(CLASS_ID, TOKEN_ID, BOB, ALICE, 55)));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(free_balance(&BOB), 55);
+		assert_eq!(Nftmart::dutch_orders((CLASS_ID, TOKEN_ID)), None);
+		assert_eq!(Nftmart::categories(CATEGORY_ID).unwrap().nft_count, 0);
+	});
+}
+
+#[test]
+fn royalty_should_be_paid() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_noop!(
+			Nftmart::create_class(
+				Origin::signed(ALICE),
+				metadata.clone(), vec![1], vec![1],
+				Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+				Perbill::from_percent(51),
+				None, None
+			),
+			Error::<Runtime>::RoyaltyRateTooHigh
+		);
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+			Perbill::from_percent(10),
+			None, None
+		));
+		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata.clone(), 1));
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata, 1));
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_ok!(Nftmart::take_order(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 100, BOB));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(free_balance(&BOB), 90);
+		assert_eq!(free_balance(&class_id_account()), 10);
+
+		assert_noop!(
+			Nftmart::update_royalty_beneficiary(Origin::signed(ALICE), CLASS_ID, ALICE),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(Nftmart::update_royalty_beneficiary(Origin::signed(class_id_account()), CLASS_ID, ALICE));
+		assert_eq!(Nftmart::royalty_beneficiary(CLASS_ID), Some(ALICE));
+
+		let alice_free = free_balance(&ALICE);
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 50, CATEGORY_ID, CLASS_ID, TOKEN_ID + 1, 1, 0, 10));
+		assert_ok!(Nftmart::take_order(Origin::signed(ALICE), CLASS_ID, TOKEN_ID + 1, 50, BOB));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::TakenOrder(CLASS_ID, TOKEN_ID + 1, BOB)));
+		assert_eq!(free_balance(&BOB), 90 + 45);
+		assert_eq!(free_balance(&ALICE), alice_free - 45);
+	});
+}
+
+#[test]
+fn platform_fee_should_be_paid() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_noop!(
+			Nftmart::update_platform_fee_rate(Origin::signed(ALICE), Perbill::from_percent(2)),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			Nftmart::update_platform_fee_rate(Origin::root(), Perbill::from_percent(51)),
+			Error::<Runtime>::PlatformFeeRateTooHigh,
+		);
+		assert_ok!(Nftmart::update_platform_fee_rate(Origin::root(), Perbill::from_percent(2)));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::UpdatedPlatformFeeRate(Perbill::zero(), Perbill::from_percent(2))));
+		assert_eq!(Nftmart::trade_proceeds(CLASS_ID, 100), (2, 0, 98));
+
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_ok!(Nftmart::take_order(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 100, BOB));
+		assert!(System::events().iter().any(|r| r.event == Event::nftmart_nft(crate::Event::PlatformFeePaid(CLASS_ID, NATIVE_CURRENCY_ID, 2))));
+		assert_eq!(free_balance(&FEE_ACCOUNT), 2);
+		assert_eq!(free_balance(&BOB), 98);
+	});
+}
+
+#[test]
+fn expired_orders_should_be_removed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable.into()),
+			Perbill::zero(),
+			None, None
+		));
+		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata.clone(), 1));
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata, 1));
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+
+		for token_id in [TOKEN_ID, TOKEN_ID + 1].iter() {
+			assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, *token_id, 1, 0, 5));
+			assert_ok!(Nftmart::submit_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, *token_id, 1, 0, 5));
+		}
+		assert_eq!(reserved_balance(&ALICE), 20);
+		assert_eq!(Nftmart::categories(CATEGORY_ID).unwrap().nft_count, 4);
+
+		for n in 2..=5 {
+			System::set_block_number(n);
+			Nftmart::on_initialize(n);
+		}
+		assert_eq!(Orders::<Runtime>::iter().count(), 4);
+
+		// At most `MaxExpiredOrdersPerBlock` steps per block.
+		System::set_block_number(6);
+		Nftmart::on_initialize(6);
+		assert_eq!(Orders::<Runtime>::iter().count(), 1);
+
+		System::set_block_number(7);
+		Nftmart::on_initialize(7);
+		assert_eq!(Orders::<Runtime>::iter().count(), 0);
+		assert_eq!(OrderExpiry::<Runtime>::iter().count(), 0);
+		assert_eq!(Nftmart::expiry_cursor(), 7);
+		assert_eq!(reserved_balance(&ALICE), 0);
+		assert_eq!(Nftmart::categories(CATEGORY_ID).unwrap().nft_count, 0);
+		assert_ok!(Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID, 1));
+	});
+}
+
+#[test]
+fn expired_listings_should_be_removed() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 3);
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_ok!(Nftmart::submit_dutch_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, CATEGORY_ID, CLASS_ID, TOKEN_ID, 100, 10, 0, 1, 5, 1));
+		let tokens = vec![(CLASS_ID, TOKEN_ID + 1), (CLASS_ID, TOKEN_ID + 2)];
+		assert_ok!(Nftmart::submit_bundle_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, CATEGORY_ID, 100, tokens, 0, 5));
+		assert_ok!(Nftmart::submit_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, CLASS_ID, 10, 2, 0, 5));
+		assert_eq!(OrderExpiry::<Runtime>::iter_prefix(5).count(), 3);
+		assert_eq!(reserved_balance(&ALICE), 20);
+
+		System::set_block_number(6);
+		assert_noop!(Nftmart::take_dutch_order(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 10), Error::<Runtime>::OrderExpired);
+
+		for n in 2..=6 {
+			System::set_block_number(n);
+			Nftmart::on_initialize(n);
+		}
+		// At most `MaxExpiredOrdersPerBlock` steps per block.
+		assert_eq!(OrderExpiry::<Runtime>::iter().count(), 1);
+
+		System::set_block_number(7);
+		Nftmart::on_initialize(7);
+		assert_eq!(Nftmart::dutch_orders((CLASS_ID, TOKEN_ID)), None);
+		assert_eq!(Nftmart::bundle_orders(0), None);
+		assert_eq!(Nftmart::offers(CLASS_ID, 1), None);
+		assert_eq!(OrderExpiry::<Runtime>::iter().count(), 0);
+		assert_eq!(reserved_balance(&ALICE), 0);
+		assert_ok!(Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID + 1, 1));
+	});
+}
+
+#[test]
+fn collection_offer_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 3);
+		assert_noop!(
+			Nftmart::submit_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, CLASS_ID_NOT_EXIST, 10, 2, 0, 10),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_ok!(Nftmart::submit_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, CLASS_ID, 10, 2, 0, 10));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::CreatedOffer(CLASS_ID, 0, ALICE)));
+		assert_eq!(reserved_balance(&ALICE), 20);
+
+		assert_noop!(Nftmart::fill_offer(Origin::signed(ALICE), CLASS_ID, 0, TOKEN_ID, 10), Error::<Runtime>::TakeOwnOrder);
+		assert_noop!(Nftmart::fill_offer(Origin::signed(BOB), CLASS_ID, 0, TOKEN_ID, 11), Error::<Runtime>::PriceTooLow);
+		assert_ok!(Nftmart::fill_offer(Origin::signed(BOB), CLASS_ID, 0, TOKEN_ID, 10));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::FilledOffer(CLASS_ID, 0, TOKEN_ID, BOB, 1)));
+		assert_eq!(Nftmart::offers(CLASS_ID, 0).unwrap().quantity, 1);
+		assert_eq!(reserved_balance(&ALICE), 10);
+
+		assert_ok!(Nftmart::fill_offer(Origin::signed(BOB), CLASS_ID, 0, TOKEN_ID + 2, 10));
+		assert_eq!(Nftmart::offers(CLASS_ID, 0), None);
+		assert_eq!(reserved_balance(&ALICE), 0);
+		assert_eq!(free_balance(&BOB), 20);
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID + 2)));
+		assert_noop!(Nftmart::fill_offer(Origin::signed(BOB), CLASS_ID, 0, TOKEN_ID + 1, 10), Error::<Runtime>::OfferNotFound);
+
+		assert_ok!(Nftmart::submit_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, CLASS_ID, 10, 2, 0, 10));
+		assert_noop!(Nftmart::remove_offer(Origin::signed(BOB), CLASS_ID, 1), Error::<Runtime>::NoPermission);
+		assert_ok!(Nftmart::remove_offer(Origin::signed(ALICE), CLASS_ID, 1));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::RemovedOffer(CLASS_ID, 1, ALICE, 0)));
+		assert_eq!(reserved_balance(&ALICE), 0);
+	});
+}
+
+#[test]
+fn bundle_order_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 3);
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		let tokens = vec![(CLASS_ID, TOKEN_ID), (CLASS_ID, TOKEN_ID + 1)];
+
+		assert_noop!(
+			Nftmart::submit_bundle_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, CATEGORY_ID, 100, vec![(CLASS_ID, TOKEN_ID)], 0, 10),
+			Error::<Runtime>::InvalidBundle
+		);
+		assert_noop!(
+			Nftmart::submit_bundle_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, CATEGORY_ID, 100, vec![(CLASS_ID, TOKEN_ID); 2], 0, 10),
+			Error::<Runtime>::InvalidBundle
+		);
+		assert_noop!(
+			Nftmart::submit_bundle_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, CATEGORY_ID, 100, tokens.clone(), 0, 10),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(Nftmart::submit_bundle_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, CATEGORY_ID, 101, tokens.clone(), 0, 10));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::CreatedBundleOrder(0, BOB)));
+		assert_noop!(Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID + 1, 1), Error::<Runtime>::OrderExists);
+		assert_noop!(Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 1), Error::<Runtime>::OrderExists);
+
+		assert_noop!(Nftmart::take_bundle_order(Origin::signed(ALICE), 0, 100), Error::<Runtime>::CanNotAfford);
+		assert_ok!(Nftmart::take_bundle_order(Origin::signed(ALICE), 0, 101));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::TakenBundleOrder(0, BOB, ALICE)));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID + 1)));
+		assert_eq!(free_balance(&BOB), 101);
+		assert_eq!(Nftmart::bundle_orders(0), None);
+		assert_eq!(Nftmart::token_bundles((CLASS_ID, TOKEN_ID)), None);
+		assert_eq!(Nftmart::categories(CATEGORY_ID).unwrap().nft_count, 0);
+
+		let tokens = vec![(CLASS_ID, TOKEN_ID), (CLASS_ID, TOKEN_ID + 1)];
+		assert_ok!(Nftmart::submit_bundle_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, CATEGORY_ID, 100, tokens, 0, 10));
+		assert_noop!(Nftmart::remove_bundle_order(Origin::signed(BOB), 1), Error::<Runtime>::NoPermission);
+		assert_ok!(Nftmart::remove_bundle_order(Origin::signed(ALICE), 1));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::RemovedBundleOrder(1, ALICE, 0)));
+		assert_ok!(Nftmart::transfer(Origin::signed(ALICE), BOB, CLASS_ID, TOKEN_ID, 1));
+	});
+}
+
+#[test]
+fn semi_fungible_token_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+			Perbill::zero(),
+			None, None
+		));
+		let (deposit, total_deposit) = Nftmart::mint_token_deposit(metadata.len() as u32, 10);
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), total_deposit).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata, 10));
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+
+		assert_noop!(
+			Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 11, 0, 10),
+			Error::<Runtime>::InvalidQuantity
+		);
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 4, 0, 10));
+		assert!(Nftmart::orders((CLASS_ID, TOKEN_ID), BOB).unwrap().by_token_owner);
+
+		// Only the quantity which is not listed can be moved.
+		assert_ok!(Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID, 6));
+		assert_noop!(Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID, 1), Error::<Runtime>::OrderExists);
+
+		assert_ok!(Nftmart::take_order(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 100, BOB));
+		assert_eq!(OrmlNFT::tokens_by_owner(ALICE, (CLASS_ID, TOKEN_ID)), 10);
+		assert!(!OrmlNFT::is_owner(&BOB, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(free_balance(&BOB), 100);
+
+		let reserved = reserved_balance(&class_id_account());
+		assert_ok!(Nftmart::burn(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 3));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::BurnedToken(ALICE, CLASS_ID, TOKEN_ID, 3)));
+		assert_eq!(OrmlNFT::tokens(CLASS_ID, TOKEN_ID).unwrap().quantity, 7);
+		assert_eq!(reserved_balance(&class_id_account()), reserved - deposit * 3);
+	});
+}
+
+#[test]
+fn transfer_from_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 2);
+		assert_noop!(
+			Nftmart::transfer_from(Origin::signed(ALICE), BOB, ALICE, CLASS_ID, TOKEN_ID, 1),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(Nftmart::approve(Origin::signed(ALICE), Some(ALICE), CLASS_ID, TOKEN_ID), orml_nft::Error::<Runtime>::NoPermission);
+
+		assert_ok!(Nftmart::approve(Origin::signed(BOB), Some(ALICE), CLASS_ID, TOKEN_ID));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::Approval(BOB, CLASS_ID, TOKEN_ID, Some(ALICE))));
+		assert_noop!(
+			Nftmart::transfer_from(Origin::signed(ALICE), BOB, ALICE, CLASS_ID, TOKEN_ID + 1, 1),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(Nftmart::transfer_from(Origin::signed(ALICE), BOB, ALICE, CLASS_ID, TOKEN_ID, 1));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(OrmlNFT::approvals((CLASS_ID, TOKEN_ID), BOB), None);
+
+		assert_ok!(Nftmart::set_approval_for_all(Origin::signed(BOB), ALICE, true));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::ApprovalForAll(BOB, ALICE, true)));
+		assert_ok!(Nftmart::transfer_from(Origin::signed(ALICE), BOB, ALICE, CLASS_ID, TOKEN_ID + 1, 1));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID + 1)));
+	});
+}
+
+#[test]
+fn update_metadata_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+		let old_class_deposit = Nftmart::create_class_deposit(1, 1, 1).0;
+		let new_class_deposit = Nftmart::create_class_deposit(3, 1, 2).0;
+		let _ = Balances::deposit_creating(&class_id_account(), new_class_deposit - old_class_deposit);
+		let reserved = reserved_balance(&class_id_account());
+
+		assert_noop!(
+			Nftmart::update_class(Origin::signed(ALICE), CLASS_ID, vec![1, 2, 3], vec![1], vec![1, 2]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(Nftmart::update_class(Origin::signed(class_id_account()), CLASS_ID, vec![1, 2, 3], vec![1], vec![1, 2]));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::UpdatedClass(CLASS_ID)));
+		let class_info = OrmlNFT::classes(CLASS_ID).unwrap();
+		assert_eq!(class_info.metadata, vec![1, 2, 3]);
+		assert_eq!(class_info.data.description, vec![1, 2]);
+		assert_eq!(class_info.data.deposit, new_class_deposit);
+		let reserved = reserved + new_class_deposit - old_class_deposit;
+		assert_eq!(reserved_balance(&class_id_account()), reserved);
+
+		assert_ok!(Nftmart::update_token_metadata(Origin::signed(class_id_account()), CLASS_ID, TOKEN_ID, vec![]));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::UpdatedToken(CLASS_ID, TOKEN_ID)));
+		assert_eq!(OrmlNFT::tokens(CLASS_ID, TOKEN_ID).unwrap().metadata, Vec::<u8>::new());
+		let unreserved = Nftmart::mint_token_deposit(1, 1).0 - Nftmart::mint_token_deposit(0, 1).0;
+		assert_eq!(reserved_balance(&class_id_account()), reserved - unreserved);
+
+		assert_ok!(Nftmart::freeze_metadata(Origin::signed(class_id_account()), CLASS_ID));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::FrozenMetadata(CLASS_ID)));
+		assert_noop!(
+			Nftmart::update_class(Origin::signed(class_id_account()), CLASS_ID, vec![1], vec![1], vec![1]),
+			Error::<Runtime>::MetadataFrozen
+		);
+		assert_noop!(
+			Nftmart::update_token_metadata(Origin::signed(class_id_account()), CLASS_ID, TOKEN_ID, vec![1]),
+			Error::<Runtime>::MetadataFrozen
+		);
+	});
+}
+
+#[test]
+fn class_admin_roles_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+		let _ = Balances::deposit_creating(&class_id_account(), 100);
+
+		assert_noop!(
+			Nftmart::add_class_admin(Origin::signed(ALICE), CLASS_ID, BOB, ClassAdminRole::Minter),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(Proxy::proxy(
+			Origin::signed(ALICE),
+			class_id_account(),
+			None,
+			Box::new(Call::Nftmart(crate::Call::add_class_admin(CLASS_ID, BOB, ClassAdminRole::Minter)))
+		));
+		assert_eq!(Proxy::proxies(class_id_account()).0.len(), 2);
+
+		// A minter can mint.
+		assert_ok!(Proxy::proxy(
+			Origin::signed(BOB),
+			class_id_account(),
+			None,
+			Box::new(Call::Nftmart(crate::Call::mint(BOB, CLASS_ID, vec![1], 1)))
+		));
+		assert_eq!(OrmlNFT::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID + 1)), 1);
+
+		// But can not touch metadata.
+		assert_ok!(Proxy::proxy(
+			Origin::signed(BOB),
+			class_id_account(),
+			None,
+			Box::new(Call::Nftmart(crate::Call::update_class(CLASS_ID, vec![2], vec![1], vec![1])))
+		));
+		assert_eq!(OrmlNFT::classes(CLASS_ID).unwrap().metadata, vec![1]);
+
+		assert_ok!(Nftmart::remove_class_admin(Origin::signed(class_id_account()), CLASS_ID, BOB, ClassAdminRole::Minter));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::RemovedClassAdmin(CLASS_ID, BOB, ClassAdminRole::Minter)));
+		assert_noop!(
+			Proxy::proxy(
+				Origin::signed(BOB),
+				class_id_account(),
+				None,
+				Box::new(Call::Nftmart(crate::Call::mint(BOB, CLASS_ID, vec![1], 1)))
+			),
+			pallet_proxy::Error::<Runtime>::NotProxy
+		);
+
+		// Minters and metadata editors do not count as admins of the class.
+		assert_ok!(Proxy::proxy(
+			Origin::signed(ALICE),
+			class_id_account(),
+			None,
+			Box::new(Call::Nftmart(crate::Call::add_class_admin(CLASS_ID, BOB, ClassAdminRole::Admin)))
+		));
+
+		// An admin is limited to the calls scoped to the class.
+		assert_ok!(Proxy::proxy(
+			Origin::signed(BOB),
+			class_id_account(),
+			None,
+			Box::new(Call::Nftmart(crate::Call::update_royalty_beneficiary(CLASS_ID, BOB)))
+		));
+		assert_eq!(Nftmart::royalty_beneficiary(CLASS_ID), Some(class_id_account()));
+
+		assert_ok!(Proxy::proxy(
+			Origin::signed(BOB),
+			class_id_account(),
+			None,
+			Box::new(Call::Nftmart(crate::Call::destroy_class(CLASS_ID, BOB)))
+		));
+		assert_eq!(
+			last_event(),
+			Event::pallet_proxy(pallet_proxy::RawEvent::ProxyExecuted(Err(DispatchError::BadOrigin)))
+		);
+		assert!(OrmlNFT::classes(CLASS_ID).is_some());
+
+		assert_ok!(Proxy::proxy(
+			Origin::signed(ALICE),
+			class_id_account(),
+			None,
+			Box::new(Call::Nftmart(crate::Call::add_class_admin(CLASS_ID, BOB, ClassAdminRole::Minter)))
+		));
+		assert_ok!(Proxy::proxy(
+			Origin::signed(ALICE),
+			class_id_account(),
+			None,
+			Box::new(Call::Proxy(pallet_proxy::Call::remove_proxy(ALICE, ProxyType::Any, 0)))
+		));
+		assert_eq!(Proxy::proxies(class_id_account()).0.len(), 2);
+		assert_noop!(
+			Nftmart::remove_class_admin(Origin::signed(class_id_account()), CLASS_ID, BOB, ClassAdminRole::Admin),
+			Error::<Runtime>::CannotRemoveLastAdmin
+		);
+		assert_ok!(Nftmart::remove_class_admin(Origin::signed(class_id_account()), CLASS_ID, BOB, ClassAdminRole::Minter));
+	});
+}
+
+#[test]
+fn mint_limits_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable),
+			Perbill::zero(),
+			Some(3), Some(2)
+		));
+		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 4).1;
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
+
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata.clone(), 2));
+		assert_eq!(Nftmart::minted_by_account(CLASS_ID, BOB), 2);
+		assert_noop!(
+			Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata.clone(), 1),
+			Error::<Runtime>::ExceedMaxMintPerAccount
+		);
+		assert_noop!(
+			Nftmart::mint(Origin::signed(class_id_account()), ALICE, CLASS_ID, metadata.clone(), 2),
+			Error::<Runtime>::ExceedMaxSupply
+		);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), ALICE, CLASS_ID, metadata.clone(), 1));
+		assert_eq!(OrmlNFT::classes(CLASS_ID).unwrap().total_issuance, 3);
+
+		assert_eq!(Nftmart::minted_by_class(CLASS_ID), 3);
+
+		// Burning does not reset the quantity minted to an account, nor to the class.
+		assert_ok!(Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 2));
+		assert_noop!(
+			Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata.clone(), 1),
+			Error::<Runtime>::ExceedMaxMintPerAccount
+		);
+		assert_noop!(
+			Nftmart::mint(Origin::signed(class_id_account()), ALICE, CLASS_ID, metadata.clone(), 1),
+			Error::<Runtime>::ExceedMaxSupply
+		);
+	});
+}
+
+#[test]
+fn public_mint_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(ALICE, 0);
+		let _ = Balances::deposit_creating(&BOB, 1000);
+		let alice_leaf = BlakeTwo256::hash_of(&ALICE);
+		let bob_leaf = BlakeTwo256::hash_of(&BOB);
+		let root = if alice_leaf <= bob_leaf {
+			BlakeTwo256::hash_of(&(alice_leaf, bob_leaf))
+		} else {
+			BlakeTwo256::hash_of(&(bob_leaf, alice_leaf))
+		};
+		let drop = DropData::<Runtime> {
+			currency_id: NATIVE_CURRENCY_ID,
+			price: 100,
+			start: 5,
+			end: 10,
+			max_per_wallet: Some(2),
+			allowlist: Some(root),
+			metadata: vec![1],
+		};
+		assert_noop!(
+			Nftmart::set_drop(Origin::signed(ALICE), CLASS_ID, drop.clone()),
+			Error::<Runtime>::NoPermission
+		);
+		let _ = Balances::deposit_creating(&class_id_account(), 100);
+		assert_ok!(Nftmart::set_drop(Origin::signed(class_id_account()), CLASS_ID, drop));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::SetDrop(CLASS_ID)));
+
+		assert_noop!(
+			Nftmart::public_mint(Origin::signed(BOB), CLASS_ID, 1, vec![alice_leaf]),
+			Error::<Runtime>::DropNotActive
+		);
+		System::set_block_number(5);
+		assert_noop!(
+			Nftmart::public_mint(Origin::signed(BOB), CLASS_ID, 1, vec![]),
+			Error::<Runtime>::NotInAllowlist
+		);
+		assert_noop!(
+			Nftmart::public_mint(Origin::signed(BOB), CLASS_ID, 1, vec![alice_leaf; 5]),
+			Error::<Runtime>::ProofTooLong
+		);
+		assert_noop!(
+			Nftmart::public_mint(Origin::signed(BOB), CLASS_ID, 3, vec![alice_leaf]),
+			Error::<Runtime>::ExceedMaxPerWallet
+		);
+
+		let class_balance = free_balance(&class_id_account());
+		let deposit = Nftmart::mint_token_deposit(1, 2).1;
+		assert_ok!(Nftmart::public_mint(Origin::signed(BOB), CLASS_ID, 2, vec![alice_leaf]));
+		assert_eq!(OrmlNFT::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID)), 2);
+		assert_eq!(free_balance(&BOB), 1000 - 200 - deposit);
+		assert_eq!(free_balance(&class_id_account()), class_balance + 200);
+		assert_noop!(
+			Nftmart::public_mint(Origin::signed(BOB), CLASS_ID, 1, vec![alice_leaf]),
+			Error::<Runtime>::ExceedMaxPerWallet
+		);
+
+		System::set_block_number(10);
+		assert_noop!(
+			Nftmart::public_mint(Origin::signed(ALICE), CLASS_ID, 1, vec![bob_leaf]),
+			Error::<Runtime>::DropNotActive
+		);
+		assert_ok!(Nftmart::remove_drop(Origin::signed(class_id_account()), CLASS_ID));
+		assert_eq!(Nftmart::drops(CLASS_ID), None);
+	});
+}
+
+#[test]
+fn drop_deposit_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(ALICE, 0);
+		let _ = Balances::deposit_creating(&class_id_account(), 100);
+		let reserved = reserved_balance(&class_id_account());
+		let mut drop = DropData::<Runtime> {
+			currency_id: NATIVE_CURRENCY_ID,
+			price: 100,
+			start: 5,
+			end: 10,
+			max_per_wallet: None,
+			allowlist: None,
+			metadata: vec![1; MaxDropMetadataLength::get() as usize + 1],
+		};
+		assert_noop!(
+			Nftmart::set_drop(Origin::signed(class_id_account()), CLASS_ID, drop.clone()),
+			Error::<Runtime>::DropMetadataTooLong
+		);
+
+		drop.metadata = vec![1; 4];
+		assert_ok!(Nftmart::set_drop(Origin::signed(class_id_account()), CLASS_ID, drop.clone()));
+		let deposit = drop.encoded_size() as Balance * MetaDataByteDeposit::get();
+		assert_eq!(reserved_balance(&class_id_account()), reserved + deposit);
+
+		// Replacing the drop only reserves the difference.
+		drop.metadata = vec![1; 8];
+		assert_ok!(Nftmart::set_drop(Origin::signed(class_id_account()), CLASS_ID, drop.clone()));
+		let deposit = drop.encoded_size() as Balance * MetaDataByteDeposit::get();
+		assert_eq!(reserved_balance(&class_id_account()), reserved + deposit);
+		assert_eq!(Nftmart::drops(CLASS_ID), Some((drop.clone(), deposit)));
+
+		assert_ok!(Nftmart::remove_drop(Origin::signed(class_id_account()), CLASS_ID));
+		assert_eq!(reserved_balance(&class_id_account()), reserved);
+
+		// Destroying the class unreserves the deposit of its drop as well.
+		assert_ok!(Nftmart::set_drop(Origin::signed(class_id_account()), CLASS_ID, drop));
+		assert_ok!(Nftmart::destroy_class(Origin::signed(class_id_account()), CLASS_ID, BOB));
+		assert_eq!(reserved_balance(&class_id_account()), Proxy::deposit(1));
+		assert_eq!(Nftmart::drops(CLASS_ID), None);
+	});
+}
+
+#[test]
+fn redeem_voucher_should_work() {
+	use sp_core::{sr25519, Pair};
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(ALICE, 0);
+		let _ = Balances::deposit_creating(&BOB, 1000);
+		let _ = Balances::deposit_creating(&class_id_account(), 100);
+		let pair = sr25519::Pair::from_seed(&[3; 32]);
+		let signer: AccountId = sp_runtime::MultiSigner::from(pair.public()).into_account();
+		let _ = Balances::deposit_creating(&signer, 1);
+
+		let metadata = vec![1, 2];
+		let voucher = MintVoucher::<Runtime> {
+			signer: signer.clone(),
+			class_id: CLASS_ID,
+			metadata_hash: BlakeTwo256::hash(&metadata),
+			quantity: 2,
+			currency_id: NATIVE_CURRENCY_ID,
+			price: 100,
+			nonce: 7,
+			expiry: 10,
+		};
+		let payload = (b"nftmart-voucher", System::block_hash(0), &voucher).encode();
+		assert_eq!(Nftmart::voucher_payload(&voucher), payload);
+		let signature = sp_runtime::MultiSignature::from(pair.sign(&payload));
+
+		// The signer is not a minter of the class yet.
+		assert_noop!(
+			Nftmart::redeem_voucher(Origin::signed(BOB), voucher.clone(), metadata.clone(), signature.clone()),
+			Error::<Runtime>::InvalidVoucher
+		);
+		assert_ok!(Proxy::proxy(
+			Origin::signed(ALICE),
+			class_id_account(),
+			None,
+			Box::new(Call::Nftmart(crate::Call::add_class_admin(CLASS_ID, signer.clone(), ClassAdminRole::Minter)))
+		));
+
+		assert_noop!(
+			Nftmart::redeem_voucher(Origin::signed(BOB), voucher.clone(), vec![1], signature.clone()),
+			Error::<Runtime>::MetadataMismatch
+		);
+		let forged = MintVoucher::<Runtime> { price: 1, ..voucher.clone() };
+		assert_noop!(
+			Nftmart::redeem_voucher(Origin::signed(BOB), forged, metadata.clone(), signature.clone()),
+			Error::<Runtime>::InvalidVoucher
+		);
+
+		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
+		assert_ok!(Nftmart::redeem_voucher(Origin::signed(BOB), voucher.clone(), metadata.clone(), signature.clone()));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::RedeemedVoucher(CLASS_ID, 7, signer.clone(), BOB)));
+		assert_eq!(OrmlNFT::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID)), 2);
+		assert_eq!(free_balance(&BOB), 1000 - 100 - deposit);
+		assert_eq!(free_balance(&signer), 101);
+
+		assert_noop!(
+			Nftmart::redeem_voucher(Origin::signed(BOB), voucher.clone(), metadata.clone(), signature.clone()),
+			Error::<Runtime>::VoucherRedeemed
+		);
+		System::set_block_number(11);
+		let voucher = MintVoucher::<Runtime> { nonce: 8, ..voucher };
+		// A signature of the bare voucher is not accepted.
+		let signature = sp_runtime::MultiSignature::from(pair.sign(&voucher.encode()));
+		assert_noop!(
+			Nftmart::redeem_voucher(Origin::signed(BOB), voucher.clone(), metadata.clone(), signature),
+			Error::<Runtime>::InvalidVoucher
+		);
+		let signature = sp_runtime::MultiSignature::from(pair.sign(&Nftmart::voucher_payload(&voucher)));
+		assert_noop!(
+			Nftmart::redeem_voucher(Origin::signed(BOB), voucher, metadata, signature),
+			Error::<Runtime>::VoucherExpired
+		);
+	});
+}
+
+#[test]
+fn rental_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+		assert_noop!(
+			Nftmart::list_rental(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, NATIVE_CURRENCY_ID, 10, 5),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			Nftmart::list_rental(Origin::signed(BOB), CLASS_ID, TOKEN_ID, NATIVE_CURRENCY_ID, 0, 5),
+			Error::<Runtime>::PriceBelowFloor
+		);
+		assert_ok!(Nftmart::list_rental(Origin::signed(BOB), CLASS_ID, TOKEN_ID, NATIVE_CURRENCY_ID, 10, 5));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::ListedRental(CLASS_ID, TOKEN_ID, BOB)));
+
+		assert_noop!(
+			Nftmart::rent(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 6),
+			Error::<Runtime>::InvalidRentalDuration
+		);
+		let alice_balance = free_balance(&ALICE);
+		assert_ok!(Nftmart::rent(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 3));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::Rented(CLASS_ID, TOKEN_ID, ALICE, 4)));
+		assert_eq!(free_balance(&ALICE), alice_balance - 30);
+		assert_eq!(free_balance(&BOB), 30);
+		assert_eq!(Nftmart::user_of(CLASS_ID, TOKEN_ID), Some(ALICE));
+		assert_eq!(OrmlNFT::owner_of((CLASS_ID, TOKEN_ID)), Some(BOB));
+
+		// The lender can neither transfer a rented token nor rent it twice.
+		assert_noop!(
+			Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID, 1),
+			Error::<Runtime>::OrderExists
+		);
+		assert_noop!(
+			Nftmart::rent(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 1),
+			Error::<Runtime>::RentalActive
+		);
+		assert_ok!(Nftmart::unlist_rental(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
+		assert_eq!(Nftmart::user_of(CLASS_ID, TOKEN_ID), Some(ALICE));
+
+		for n in 2..=4 {
+			System::set_block_number(n);
+			Nftmart::on_initialize(n);
+		}
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::RentalExpired(CLASS_ID, TOKEN_ID, ALICE)));
+		assert_eq!(Nftmart::user_of(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(Nftmart::rentals((CLASS_ID, TOKEN_ID)), None);
+		assert_ok!(Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID, 1));
+	});
+}
+
+#[test]
+fn expired_rentals_should_be_removed() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 3);
+		for token_id in TOKEN_ID..TOKEN_ID + 3 {
+			assert_ok!(Nftmart::list_rental(Origin::signed(BOB), CLASS_ID, token_id, NATIVE_CURRENCY_ID, 10, 5));
+			assert_ok!(Nftmart::rent(Origin::signed(ALICE), CLASS_ID, token_id, 3));
+			assert_ok!(Nftmart::unlist_rental(Origin::signed(BOB), CLASS_ID, token_id));
+		}
+
+		for n in 2..=4 {
+			System::set_block_number(n);
+			Nftmart::on_initialize(n);
+		}
+		// At most `MaxExpiredRentalsPerBlock` steps per block.
+		assert_eq!(Rentals::<Runtime>::iter().count(), 1);
+		// A rental which has ended does not lock the token before it is removed.
+		let (token, _) = Rentals::<Runtime>::iter().next().unwrap();
+		assert_eq!(Nftmart::user_of(token.0, token.1), None);
+		assert_ok!(Nftmart::transfer(Origin::signed(BOB), ALICE, token.0, token.1, 1));
+
+		System::set_block_number(5);
+		Nftmart::on_initialize(5);
+		assert_eq!(Rentals::<Runtime>::iter().count(), 0);
+		assert_eq!(RentalExpiry::<Runtime>::iter().count(), 0);
+		assert_eq!(Nftmart::rental_expiry_cursor(), 6);
+	});
+}
+
+#[test]
+fn fractionalize_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+		assert_noop!(
+			Nftmart::fractionalize(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 100, NATIVE_CURRENCY_ID, 300),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			Nftmart::fractionalize(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 100, NATIVE_CURRENCY_ID, 0),
+			Error::<Runtime>::PriceBelowFloor
+		);
+		assert_ok!(Nftmart::fractionalize(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 100, NATIVE_CURRENCY_ID, 300));
+		// The currency IDs below `FirstCreatedCurrencyId` are left to accepted currencies.
+		let share_currency_id = FirstCreatedCurrencyId::get();
+		assert_eq!(
+			last_event(),
+			Event::nftmart_nft(crate::Event::Fractionalized(CLASS_ID, TOKEN_ID, share_currency_id, 100))
+		);
+		assert_eq!(OrmlNFT::owner_of((CLASS_ID, TOKEN_ID)), Some(Nftmart::vault_account()));
+		assert_eq!(Currencies::free_balance(share_currency_id, &BOB), 100);
+
+		assert_ok!(<Currencies as MultiCurrency<AccountId>>::transfer(share_currency_id, &BOB, &ALICE, 40));
+		assert_noop!(
+			Nftmart::redeem_fractions(Origin::signed(BOB), CLASS_ID, TOKEN_ID),
+			Error::<Runtime>::InsufficientShares
+		);
+		assert_noop!(
+			Nftmart::claim_proceeds(Origin::signed(BOB), CLASS_ID, TOKEN_ID),
+			Error::<Runtime>::NotBoughtOut
+		);
+
+		let alice_balance = free_balance(&ALICE);
+		assert_ok!(Nftmart::buyout(Origin::signed(ALICE), CLASS_ID, TOKEN_ID));
+		assert_eq!(OrmlNFT::owner_of((CLASS_ID, TOKEN_ID)), Some(ALICE));
+		assert_eq!(free_balance(&ALICE), alice_balance - 300);
+		assert_noop!(
+			Nftmart::buyout(Origin::signed(ALICE), CLASS_ID, TOKEN_ID),
+			Error::<Runtime>::BoughtOut
+		);
+
+		assert_ok!(Nftmart::claim_proceeds(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::ClaimedProceeds(CLASS_ID, TOKEN_ID, BOB, 60, 180)));
+		assert_eq!(free_balance(&BOB), 180);
+		assert_ok!(Nftmart::claim_proceeds(Origin::signed(ALICE), CLASS_ID, TOKEN_ID));
+		assert_eq!(free_balance(&ALICE), alice_balance - 300 + 120);
+		assert_eq!(Currencies::total_issuance(share_currency_id), 0);
+		assert_eq!(Nftmart::vaults((CLASS_ID, TOKEN_ID)), None);
+	});
+}
+
+#[test]
+fn fractionalize_after_buyout_should_wait_for_proceeds() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+		assert_ok!(Nftmart::fractionalize(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 100, NATIVE_CURRENCY_ID, 300));
+		let share_currency_id = FirstCreatedCurrencyId::get();
+		assert_ok!(Nftmart::buyout(Origin::signed(ALICE), CLASS_ID, TOKEN_ID));
+
+		// The buyer owns the token, but BOB has not claimed the proceeds yet.
+		assert_noop!(
+			Nftmart::fractionalize(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 10, NATIVE_CURRENCY_ID, 500),
+			Error::<Runtime>::VaultExists
+		);
+		assert_ok!(Nftmart::claim_proceeds(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
+		assert_eq!(free_balance(&BOB), 300);
+		assert_eq!(Currencies::total_issuance(share_currency_id), 0);
+
+		assert_ok!(Nftmart::fractionalize(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 10, NATIVE_CURRENCY_ID, 500));
+		assert_eq!(Nftmart::vaults((CLASS_ID, TOKEN_ID)).unwrap().share_currency_id, share_currency_id + 1);
+	});
+}
+
+#[test]
+fn redeem_fractions_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+		assert_ok!(Nftmart::fractionalize(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 100, NATIVE_CURRENCY_ID, 300));
+		assert_ok!(Nftmart::redeem_fractions(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
+		assert_eq!(OrmlNFT::owner_of((CLASS_ID, TOKEN_ID)), Some(BOB));
+		assert_eq!(Currencies::free_balance(NATIVE_CURRENCY_ID + 1, &BOB), 0);
+		assert_eq!(Nftmart::vaults((CLASS_ID, TOKEN_ID)), None);
+	});
+}
+
+#[test]
+fn nested_token_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 2);
+		let child = TOKEN_ID + 1;
+		assert_noop!(
+			Nftmart::nest(Origin::signed(ALICE), CLASS_ID, child, CLASS_ID, TOKEN_ID),
+			orml_nft::Error::<Runtime>::NoPermission
+		);
+		assert_ok!(Nftmart::nest(Origin::signed(BOB), CLASS_ID, child, CLASS_ID, TOKEN_ID));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::Nested(CLASS_ID, child, CLASS_ID, TOKEN_ID)));
+
+		// Transferring the parent moves the child as well.
+		assert_ok!(Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID, 1));
+		assert_eq!(OrmlNFT::root_owner((CLASS_ID, child)), Some(ALICE));
+		assert_noop!(
+			Nftmart::transfer(Origin::signed(BOB), BOB, CLASS_ID, child, 1),
+			orml_nft::Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			Nftmart::burn(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 1),
+			orml_nft::Error::<Runtime>::HasChildren
+		);
+
+		// The owner of the root transfers the child directly.
+		assert_ok!(Nftmart::transfer(Origin::signed(ALICE), BOB, CLASS_ID, child, 1));
+		assert_eq!(OrmlNFT::tokens_by_owner(BOB, (CLASS_ID, child)), 1);
+		assert_eq!(OrmlNFT::parents((CLASS_ID, child)), None);
+		assert_noop!(
+			Nftmart::unnest(Origin::signed(BOB), CLASS_ID, child),
+			Error::<Runtime>::NotNested
+		);
+	});
+}
+
+#[test]
+fn revoke_and_reassign_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_noop!(
+			Nftmart::create_class(
+				Origin::signed(ALICE),
+				metadata.clone(), vec![1], vec![1],
+				Properties(ClassProperty::Transferable | ClassProperty::Revocable),
+				Perbill::zero(),
+				None, None
+			),
+			Error::<Runtime>::InvalidProperties
+		);
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Revocable.into()),
+			Perbill::zero(),
+			None, None
+		));
+		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 3).1;
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata, 3));
+		let reserved = reserved_balance(&class_id_account());
+
+		assert_noop!(
+			Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID, 1),
+			Error::<Runtime>::NonTransferable
+		);
+		assert_noop!(
+			Nftmart::revoke(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, BOB, 1),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			Nftmart::revoke(Origin::signed(class_id_account()), CLASS_ID, TOKEN_ID, BOB, 4),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(Nftmart::reassign(Origin::signed(class_id_account()), CLASS_ID, TOKEN_ID, BOB, ALICE, 1));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::ReassignedToken(CLASS_ID, TOKEN_ID, BOB, ALICE, 1)));
+		assert_eq!(OrmlNFT::tokens_by_owner(ALICE, (CLASS_ID, TOKEN_ID)), 1);
+		assert_eq!(OrmlNFT::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID)), 2);
+
+		// The holder can neither sell nor lend the token, and listings made before are removed on revocation.
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_noop!(
+			Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10),
+			Error::<Runtime>::NonTransferable
+		);
+		assert_noop!(
+			Nftmart::list_rental(Origin::signed(BOB), CLASS_ID, TOKEN_ID, NATIVE_CURRENCY_ID, 1, 10),
+			Error::<Runtime>::NonTransferable
+		);
+		RentalListings::<Runtime>::insert((CLASS_ID, TOKEN_ID), RentalListing {
+			owner: BOB,
+			currency_id: NATIVE_CURRENCY_ID,
+			price_per_block: 1,
+			max_duration: 10,
+		});
+
+		// The deposit goes back to the class owner.
+		assert_ok!(Nftmart::revoke(Origin::signed(class_id_account()), CLASS_ID, TOKEN_ID, BOB, 2));
+		assert!(Nftmart::rental_listings((CLASS_ID, TOKEN_ID)).is_none());
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::RevokedToken(CLASS_ID, TOKEN_ID, BOB, 2)));
+		assert_eq!(OrmlNFT::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID)), 0);
+		assert_eq!(reserved_balance(&class_id_account()), reserved - deposit / 3 * 2);
+	});
+}
+
+#[test]
+fn revoke_should_fail_for_non_revocable_class() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+		assert_noop!(
+			Nftmart::revoke(Origin::signed(class_id_account()), CLASS_ID, TOKEN_ID, BOB, 1),
+			Error::<Runtime>::NonRevocable
+		);
+	});
+}
+
+#[test]
+fn attributes_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+		let _ = Balances::deposit_creating(&class_id_account(), 100);
+		let reserved = reserved_balance(&class_id_account());
+		let byte_deposit = <Runtime as Config>::MetaDataByteDeposit::get();
+
+		assert_noop!(
+			Nftmart::set_class_attribute(Origin::signed(ALICE), CLASS_ID, b"rarity".to_vec(), AttributeValue::Bool(true)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			Nftmart::set_class_attribute(Origin::signed(class_id_account()), CLASS_ID, b"too long key".to_vec(), AttributeValue::Bool(true)),
+			Error::<Runtime>::AttributeKeyTooLong
+		);
+		assert_noop!(
+			Nftmart::set_class_attribute(Origin::signed(class_id_account()), CLASS_ID, b"rarity".to_vec(), AttributeValue::Bytes(vec![0; 16])),
+			Error::<Runtime>::AttributeValueTooLong
+		);
+
+		assert_ok!(Nftmart::set_class_attribute(Origin::signed(class_id_account()), CLASS_ID, b"rarity".to_vec(), AttributeValue::Bytes(b"epic".to_vec())));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::SetClassAttribute(CLASS_ID, b"rarity".to_vec())));
+		assert_eq!(Nftmart::class_attributes(CLASS_ID, b"rarity".to_vec()), Some((AttributeValue::Bytes(b"epic".to_vec()), 12 * byte_deposit)));
+		assert_eq!(reserved_balance(&class_id_account()), reserved + 12 * byte_deposit);
+		assert_ok!(Nftmart::clear_class_attribute(Origin::signed(class_id_account()), CLASS_ID, b"rarity".to_vec()));
+		assert_eq!(reserved_balance(&class_id_account()), reserved);
+		assert_noop!(
+			Nftmart::clear_class_attribute(Origin::signed(class_id_account()), CLASS_ID, b"rarity".to_vec()),
+			Error::<Runtime>::AttributeNotFound
+		);
+
+		assert_noop!(
+			Nftmart::set_token_attribute(Origin::signed(class_id_account()), CLASS_ID, TOKEN_ID_NOT_EXIST, b"level".to_vec(), AttributeValue::Integer(3)),
+			Error::<Runtime>::TokenIdNotFound
+		);
+		assert_ok!(Nftmart::set_token_attribute(Origin::signed(class_id_account()), CLASS_ID, TOKEN_ID, b"level".to_vec(), AttributeValue::Integer(3)));
+		assert_eq!(reserved_balance(&class_id_account()), reserved + 14 * byte_deposit);
+		// Overwriting an attribute adjusts the deposit.
+		assert_ok!(Nftmart::set_token_attribute(Origin::signed(class_id_account()), CLASS_ID, TOKEN_ID, b"level".to_vec(), AttributeValue::Bool(true)));
+		assert_eq!(reserved_balance(&class_id_account()), reserved + 7 * byte_deposit);
+
+		// Burning the token clears its attributes.
+		assert_ok!(Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 1));
+		assert_eq!(Nftmart::token_attributes((CLASS_ID, TOKEN_ID), b"level".to_vec()), None);
+		assert_eq!(reserved_balance(&class_id_account()), reserved - Nftmart::mint_token_deposit(1, 1).0);
+	});
+}
+
+#[test]
+fn query_helpers_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable.into()),
+			Perbill::zero(),
+			None, None
+		));
+		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 3).1;
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
+		for _ in 0..3 {
+			assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata.clone(), 1));
+		}
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_eq!(Nftmart::tokens_of(&BOB, 0, 2).len(), 2);
+		assert_eq!(Nftmart::tokens_of(&BOB, 1, 2).len(), 1);
+		assert_eq!(Nftmart::tokens_of(&BOB, 2, 2).len(), 0);
+		assert_eq!(Nftmart::tokens_of(&ALICE, 0, 2).len(), 0);
+		assert_eq!(Nftmart::class_info(CLASS_ID).unwrap().total_issuance, 3);
+		assert_eq!(Nftmart::token_info(CLASS_ID, TOKEN_ID).unwrap().quantity, 1);
+		assert!(Nftmart::class_info(CLASS_ID_NOT_EXIST).is_none());
+
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_ok!(Nftmart::submit_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_eq!(Nftmart::orders_of_token(CLASS_ID, TOKEN_ID, 0, 10).len(), 2);
+		assert_eq!(Nftmart::orders_of_token(CLASS_ID, TOKEN_ID, 0, 1).len(), 1);
+		assert_eq!(Nftmart::orders_of_token(CLASS_ID, TOKEN_ID, 1, 1).len(), 1);
+		assert_eq!(Nftmart::orders_of_token(CLASS_ID, TOKEN_ID + 1, 0, 10).len(), 0);
+		assert_eq!(Nftmart::orders_of_category(CATEGORY_ID, 0, 1).len(), 1);
+		assert_eq!(Nftmart::orders_of_category(CATEGORY_ID, 2, 1).len(), 0);
+		assert_eq!(Nftmart::orders_by_account(&ALICE, 0, 10), vec![OrderInfo {
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+			owner: ALICE,
+			currency_id: NATIVE_CURRENCY_ID,
+			price: 10,
+			deposit: 0,
+			deadline: 10,
+			category_id: CATEGORY_ID,
+			quantity: 1,
+			by_token_owner: false,
+		}]);
+		assert_eq!(Nftmart::all_categories(1, 10), vec![]);
+		assert_eq!(Nftmart::all_categories(0, 10), vec![(CATEGORY_ID, CategoryData { metadata: vec![1], nft_count: 2 })]);
+	});
+}
+
+#[test]
+fn order_indexes_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable.into()),
+			Perbill::zero(),
+			None, None
+		));
+		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata.clone(), 1));
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata, 1));
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID + 1, 1, 0, 10));
+		assert_ok!(Nftmart::submit_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_eq!(OrdersByAccount::<Runtime>::iter_prefix(&BOB).count(), 2);
+		assert_eq!(Nftmart::orders_by_account(&ALICE, 0, 10).len(), 1);
+		assert_eq!(Nftmart::orders_of_category(CATEGORY_ID, 0, 10).len() as Balance, Nftmart::categories(CATEGORY_ID).unwrap().nft_count);
+
+		assert_ok!(Nftmart::remove_order(Origin::signed(BOB), CLASS_ID, TOKEN_ID + 1));
+		assert_ok!(Nftmart::take_order(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 100, BOB));
+		assert_eq!(OrdersByAccount::<Runtime>::iter().count(), 0);
+		assert_eq!(OrdersByCategory::<Runtime>::iter().count(), 0);
+		assert_eq!(Nftmart::categories(CATEGORY_ID).unwrap().nft_count, 0);
+	});
+}
+
+#[test]
+fn migrate_order_indexes_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable.into()),
+			Perbill::zero(),
+			None, None
+		));
+		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 1).1;
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata, 1));
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_ok!(Nftmart::submit_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		OrdersByAccount::<Runtime>::remove_all();
+		OrdersByCategory::<Runtime>::remove_all();
+		Categories::<Runtime>::mutate(CATEGORY_ID, |category| category.as_mut().unwrap().nft_count = 3);
+
+		migrations::migrate_order_indexes::<Runtime>();
+		assert_eq!(Nftmart::orders_by_account_index(&BOB, (CLASS_ID, TOKEN_ID)), Some(()));
+		assert_eq!(Nftmart::orders_by_account_index(&ALICE, (CLASS_ID, TOKEN_ID)), Some(()));
+		assert_eq!(Nftmart::orders_of_category(CATEGORY_ID, 0, 10).len(), 2);
+		assert_eq!(Nftmart::categories(CATEGORY_ID).unwrap().nft_count, 2);
+	});
+}
+
+#[test]
+fn migrate_sft_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable.into()),
+			Perbill::zero(),
+			None, None
+		));
+		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 1).1;
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata, 1));
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_ok!(Nftmart::submit_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+
+		// Rewrite the token and its orders in the layout of `Releases::V4_0_0`.
+		let token = OrmlNFT::tokens(CLASS_ID, TOKEN_ID).unwrap();
+		frame_support::storage::unhashed::put(
+			&orml_nft::Tokens::<Runtime>::hashed_key_for(CLASS_ID, TOKEN_ID),
+			&migrations::TokenInfoV4 { metadata: token.metadata, owner: BOB, data: token.data },
+		);
+		for who in vec![BOB, ALICE] {
+			let order = Nftmart::orders((CLASS_ID, TOKEN_ID), &who).unwrap();
+			frame_support::storage::unhashed::put(
+				&Orders::<Runtime>::hashed_key_for((CLASS_ID, TOKEN_ID), &who),
+				&migrations::OrderDataV4::<Runtime> {
+					currency_id: order.currency_id,
+					price: order.price,
+					deposit: order.deposit,
+					deadline: order.deadline,
+					category_id: order.category_id,
+				},
+			);
+		}
+		orml_nft::OwnersByToken::<Runtime>::remove_all();
+		orml_nft::TokensByOwner::<Runtime>::remove_all();
+		// A stale entry of the old index is dropped, the owner stored in the token wins.
+		frame_support::storage::unhashed::put(
+			&orml_nft::TokensByOwner::<Runtime>::hashed_key_for(&ALICE, (CLASS_ID, TOKEN_ID)),
+			&(),
+		);
+
+		migrations::migrate_sft::<Runtime>();
+		assert_eq!(OrmlNFT::tokens(CLASS_ID, TOKEN_ID).unwrap().quantity, 1);
+		assert_eq!(OrmlNFT::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID)), 1);
+		assert!(!orml_nft::TokensByOwner::<Runtime>::contains_key(ALICE, (CLASS_ID, TOKEN_ID)));
+		assert!(orml_nft::OwnersByToken::<Runtime>::contains_key((CLASS_ID, TOKEN_ID), BOB));
+
+		let order = Nftmart::orders((CLASS_ID, TOKEN_ID), BOB).unwrap();
+		assert_eq!((order.price, order.quantity, order.by_token_owner), (100, 1, true));
+		let order = Nftmart::orders((CLASS_ID, TOKEN_ID), ALICE).unwrap();
+		assert_eq!((order.price, order.quantity, order.by_token_owner), (10, 1, false));
+	});
+}
+
+#[test]
+fn migrate_royalty_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 1);
+
+		// Rewrite the class in the layout of `Releases::V2_0_0`.
+		let class = OrmlNFT::classes(CLASS_ID).unwrap();
+		frame_support::storage::unhashed::put(
+			&orml_nft::Classes::<Runtime>::hashed_key_for(CLASS_ID),
+			&orml_nft::ClassInfo::<TokenIdOf<Runtime>, AccountId, migrations::ClassDataV2<BlockNumberOf<Runtime>>> {
+				metadata: class.metadata.clone(),
+				total_issuance: 1,
+				owner: class.owner.clone(),
+				data: migrations::ClassDataV2 {
+					deposit: class.data.deposit,
+					properties: class.data.properties,
+					name: class.data.name.clone(),
+					description: class.data.description.clone(),
+					create_block: class.data.create_block,
+				},
+			},
+		);
+
+		// `migrate_royalty` writes the layout of `Releases::V5_0_0`, upgraded by `migrate_supply`.
+		migrations::migrate_royalty::<Runtime>();
+		migrations::migrate_supply::<Runtime>();
+		let migrated = OrmlNFT::classes(CLASS_ID).unwrap();
+		assert_eq!(migrated.total_issuance, 1);
+		assert_eq!(migrated.data.deposit, class.data.deposit);
+		assert_eq!(migrated.data.name, class.data.name);
+		assert_eq!(migrated.data.create_block, class.data.create_block);
+		assert_eq!(migrated.data.royalty_rate, Perbill::zero());
+	});
+}
+
+#[test]
+fn migrate_supply_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 2);
+
+		// Rewrite the class in the layout of `Releases::V5_0_0`.
+		let class = OrmlNFT::classes(CLASS_ID).unwrap();
+		frame_support::storage::unhashed::put(
+			&orml_nft::Classes::<Runtime>::hashed_key_for(CLASS_ID),
+			&orml_nft::ClassInfo::<TokenIdOf<Runtime>, AccountId, migrations::ClassDataV5<BlockNumberOf<Runtime>>> {
+				metadata: class.metadata.clone(),
+				total_issuance: 2,
+				owner: class.owner.clone(),
+				data: migrations::ClassDataV5 {
+					deposit: class.data.deposit,
+					properties: class.data.properties,
+					name: class.data.name.clone(),
+					description: class.data.description.clone(),
+					create_block: class.data.create_block,
+					royalty_rate: Perbill::from_percent(5),
+				},
+			},
+		);
+
+		migrations::migrate_supply::<Runtime>();
+		let migrated = OrmlNFT::classes(CLASS_ID).unwrap();
+		assert_eq!(migrated.total_issuance, 2);
+		assert_eq!(migrated.owner, class.owner);
+		assert_eq!(migrated.data.royalty_rate, Perbill::from_percent(5));
+		assert_eq!(migrated.data.create_block, class.data.create_block);
+		assert_eq!(migrated.data.max_supply, None);
+		assert_eq!(migrated.data.max_mint_per_account, None);
+	});
+}
+
+#[test]
+fn crossing_orders_should_match() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable.into()),
+			Perbill::zero(),
+			None, None
+		));
+		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 1).1;
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata, 1));
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+
+		// A bid below the ask rests on the book.
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_ok!(Nftmart::submit_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 90, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_eq!(Orders::<Runtime>::iter().count(), 2);
+		assert_eq!(reserved_balance(&ALICE), 90);
+
+		// No more orders fit in the book of the token.
+		let charlie = AccountId::new([3u8; 32]);
+		let _ = Balances::deposit_creating(&charlie, 100);
+		assert_noop!(
+			Nftmart::submit_order(Origin::signed(charlie), NATIVE_CURRENCY_ID, 50, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10),
+			Error::<Runtime>::TooManyOrders,
+		);
+
+		// A new ask below the bid is filled at the bid price.
+		assert_ok!(Nftmart::remove_order(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 80, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::TakenOrder(CLASS_ID, TOKEN_ID, ALICE)));
+		assert_eq!(Orders::<Runtime>::iter().count(), 0);
+		assert_eq!(Nftmart::categories(CATEGORY_ID).unwrap().nft_count, 0);
+		assert_eq!(orml_nft::Module::<Runtime>::owner_of((CLASS_ID, TOKEN_ID)), Some(ALICE));
+		assert_eq!(free_balance(&BOB), 90);
+		assert_eq!(reserved_balance(&ALICE), 0);
+
+		// A new bid above the ask is filled at the ask price.
+		assert_ok!(Nftmart::submit_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 60, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 70, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::TakenOrder(CLASS_ID, TOKEN_ID, ALICE)));
+		assert_eq!(orml_nft::Module::<Runtime>::owner_of((CLASS_ID, TOKEN_ID)), Some(BOB));
+		assert_eq!(free_balance(&BOB), 30);
+		assert_eq!(Orders::<Runtime>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn accepted_currencies_should_be_enforced() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable.into()),
+			Perbill::zero(),
+			None, None
+		));
+		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 1).1;
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata, 1));
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+
+		assert_noop!(
+			Nftmart::submit_order(Origin::signed(BOB), 1, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10),
+			Error::<Runtime>::CurrencyNotAccepted,
+		);
+		assert_ok!(NftmartConfig::accept_currency(Origin::root(), NATIVE_CURRENCY_ID, 50));
+		assert_noop!(
+			Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 40, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10),
+			Error::<Runtime>::PriceBelowFloor,
+		);
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 60, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert_noop!(
+			Nftmart::update_order_price(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 10),
+			Error::<Runtime>::PriceBelowFloor,
+		);
+		assert_noop!(
+			Nftmart::submit_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, CLASS_ID, 10, 1, 0, 10),
+			Error::<Runtime>::PriceBelowFloor,
+		);
+
+		// The class only accepts currency 1.
+		assert_noop!(
+			Nftmart::set_class_currencies(Origin::signed(class_id_account()), CLASS_ID, vec![1]),
+			Error::<Runtime>::CurrencyNotAccepted,
+		);
+		assert_ok!(NftmartConfig::accept_currency(Origin::root(), 1, 0));
+		assert_noop!(
+			Nftmart::set_class_currencies(Origin::signed(ALICE), CLASS_ID, vec![1]),
+			Error::<Runtime>::NoPermission,
+		);
+		assert_noop!(
+			Nftmart::set_class_currencies(Origin::signed(class_id_account()), CLASS_ID, vec![1, 1, 1]),
+			Error::<Runtime>::TooManyCurrencies,
+		);
+		let _ = Balances::deposit_creating(&class_id_account(), 100);
+		let reserved = reserved_balance(&class_id_account());
+		assert_ok!(Nftmart::set_class_currencies(Origin::signed(class_id_account()), CLASS_ID, vec![1]));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::UpdatedClassCurrencies(CLASS_ID)));
+		assert_eq!(reserved_balance(&class_id_account()), reserved + 5);
+		assert_noop!(
+			Nftmart::submit_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, CLASS_ID, 100, 1, 0, 10),
+			Error::<Runtime>::CurrencyNotAccepted,
+		);
+		assert_noop!(
+			Nftmart::create_auction(Origin::signed(BOB), NATIVE_CURRENCY_ID, CLASS_ID, TOKEN_ID, 100, 10, 0, 1, 10),
+			Error::<Runtime>::CurrencyNotAccepted,
+		);
+		// A zero floor still rejects a zero price.
+		assert_noop!(
+			Nftmart::submit_offer(Origin::signed(ALICE), 1, CLASS_ID, 0, 1, 0, 10),
+			Error::<Runtime>::PriceBelowFloor,
+		);
+
+		assert_ok!(Nftmart::set_class_currencies(Origin::signed(class_id_account()), CLASS_ID, vec![]));
+		assert_eq!(Nftmart::class_currencies(CLASS_ID), None);
+		assert_eq!(reserved_balance(&class_id_account()), reserved);
+		assert_ok!(Nftmart::submit_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, CLASS_ID, 100, 1, 0, 10));
+	});
+}
+
+#[test]
+fn escrowed_order_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		add_class_and_mint(BOB, 2);
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		let escrow = Nftmart::order_escrow_account();
+		assert_noop!(
+			Nftmart::submit_escrowed_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10),
+			Error::<Runtime>::CannotEscrowBid,
+		);
+
+		// The token is returned when the order is removed.
+		assert_ok!(Nftmart::submit_escrowed_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		assert!(System::events().iter().any(|r| r.event == Event::nftmart_nft(crate::Event::EscrowedToken(CLASS_ID, TOKEN_ID, BOB, 1))));
+		assert_eq!(orml_nft::Module::<Runtime>::owner_of((CLASS_ID, TOKEN_ID)), Some(escrow.clone()));
+		assert_noop!(
+			Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID, 1),
+			Error::<Runtime>::NoPermission,
+		);
+		assert_ok!(Nftmart::remove_order(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::ReleasedToken(CLASS_ID, TOKEN_ID, BOB, 1)));
+		assert_eq!(orml_nft::Module::<Runtime>::owner_of((CLASS_ID, TOKEN_ID)), Some(BOB));
+
+		// The token goes from escrow to the buyer when the order is taken.
+		assert_ok!(Nftmart::submit_escrowed_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID, 1, 0, 10));
+		System::reset_events();
+		assert_ok!(Nftmart::take_order(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 100, BOB));
+		assert!(System::events().iter().any(|r| r.event == Event::nftmart_nft(crate::Event::SoldEscrowedToken(CLASS_ID, TOKEN_ID, BOB, ALICE, 1))));
+		assert!(!System::events().iter().any(|r| matches!(r.event, Event::nftmart_nft(crate::Event::ReleasedToken(..)))));
+		assert_eq!(orml_nft::Module::<Runtime>::owner_of((CLASS_ID, TOKEN_ID)), Some(ALICE));
+		assert_eq!(free_balance(&BOB), 100);
+		assert_eq!(EscrowedOrders::<Runtime>::iter().count(), 0);
+
+		// The token is returned when the order expires.
+		assert_ok!(Nftmart::submit_escrowed_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 100, CATEGORY_ID, CLASS_ID, TOKEN_ID + 1, 1, 0, 5));
+		assert_eq!(orml_nft::Module::<Runtime>::owner_of((CLASS_ID, TOKEN_ID + 1)), Some(escrow));
+		for n in 2..=10 {
+			System::set_block_number(n);
+			Nftmart::on_initialize(n);
+		}
+		assert_eq!(Orders::<Runtime>::iter().count(), 0);
+		assert_eq!(orml_nft::Module::<Runtime>::owner_of((CLASS_ID, TOKEN_ID + 1)), Some(BOB));
+		assert_eq!(Nftmart::categories(CATEGORY_ID).unwrap().nft_count, 0);
+	});
+}
@@ -52,7 +52,7 @@ use orml_traits::{
 	account::MergeAccount,
 	arithmetic::{Signed, SimpleArithmetic},
 	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
-	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
+	CreateCurrency, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
 };
 use orml_utilities::with_transaction_result;
 use sp_runtime::{
@@ -192,6 +192,23 @@ pub mod module {
 	}
 }
 
+impl<T: Config> CreateCurrency<T::AccountId> for Pallet<T>
+where
+	T::MultiCurrency: CreateCurrency<T::AccountId, CurrencyId = CurrencyIdOf<T>>,
+{
+	type CurrencyId = CurrencyIdOf<T>;
+
+	fn create_currency(owner: &T::AccountId) -> result::Result<Self::CurrencyId, DispatchError> {
+		let currency_id = T::MultiCurrency::create_currency(owner)?;
+		if currency_id == T::GetNativeCurrencyId::get() {
+			// The native currency is never created again, the next one is free.
+			T::MultiCurrency::create_currency(owner)
+		} else {
+			Ok(currency_id)
+		}
+	}
+}
+
 impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 	type CurrencyId = CurrencyIdOf<T>;
 	type Balance = BalanceOf<T>;
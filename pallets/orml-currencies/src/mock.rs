@@ -69,6 +69,7 @@ parameter_type_with_key! {
 
 parameter_types! {
 	pub DustAccount: AccountId = ModuleId(*b"orml/dst").into_account();
+	pub const FirstCreatedCurrencyId: CurrencyId = 0;
 }
 
 impl orml_tokens::Config for Runtime {
@@ -79,6 +80,7 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = orml_tokens::TransferDust<Runtime, DustAccount>;
+	type FirstCreatedCurrencyId = FirstCreatedCurrencyId;
 }
 
 pub const NATIVE_CURRENCY_ID: CurrencyId = 1;
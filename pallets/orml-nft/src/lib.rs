@@ -9,6 +9,16 @@
 //!
 //! This module provides basic functions to create and manager
 //! NFT(non fungible token) such as `create_class`, `transfer`, `mint`, `burn`.
+//!
+//! A token carries a total quantity which may be split between several
+//! accounts, `TokensByOwner` keeps the balance of each holder.
+//!
+//! A holder may approve another account to transfer one of its tokens, or
+//! approve an operator to transfer all of its tokens.
+//!
+//! A token may own other tokens, its children are held by the account of the
+//! token so they move together with it. Nesting is bounded by
+//! `MaxNestingDepth`.
 
 //! ### Module Functions
 //!
@@ -16,6 +26,8 @@
 //! - `transfer` - Transfer NFT(non fungible token) to another account.
 //! - `mint` - Mint NFT(non fungible token)
 //! - `burn` - Burn NFT(non fungible token)
+//! - `nest` - Nest NFT(non fungible token) into another one
+//! - `unnest` - Take a nested NFT(non fungible token) out of its parent
 //! - `destroy_class` - Destroy NFT(non fungible token) class
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -24,7 +36,7 @@
 use codec::{Decode, Encode};
 use frame_support::{ensure, pallet_prelude::*, Parameter};
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, One, Zero},
+	traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, Convert, MaybeSerializeDeserialize, Member, One, Zero},
 	DispatchError, DispatchResult, RuntimeDebug,
 };
 use sp_std::vec::Vec;
@@ -34,11 +46,11 @@ mod tests;
 
 /// Class info
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
-pub struct ClassInfo<TokenId, AccountId, Data> {
+pub struct ClassInfo<Quantity, AccountId, Data> {
 	/// Class metadata
 	pub metadata: Vec<u8>,
 	/// Total issuance for the class
-	pub total_issuance: TokenId,
+	pub total_issuance: Quantity,
 	/// Class owner
 	pub owner: AccountId,
 	/// Class Properties
@@ -47,11 +59,11 @@ pub struct ClassInfo<TokenId, AccountId, Data> {
 
 /// Token info
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
-pub struct TokenInfo<AccountId, Data> {
+pub struct TokenInfo<Quantity, Data> {
 	/// Token metadata
 	pub metadata: Vec<u8>,
-	/// Token owner
-	pub owner: AccountId,
+	/// Total quantity of the token
+	pub quantity: Quantity,
 	/// Token Properties
 	pub data: Data,
 }
@@ -68,20 +80,28 @@ pub mod module {
 		type ClassId: Parameter + Member + AtLeast32BitUnsigned + Default + Copy + MaybeSerializeDeserialize + codec::FullCodec;
 		/// The token ID type
 		type TokenId: Parameter + Member + AtLeast32BitUnsigned + Default + Copy + MaybeSerializeDeserialize + codec::FullCodec;
+		/// The token quantity type
+		type Quantity: Parameter + Member + AtLeast32BitUnsigned + Default + Copy + MaybeSerializeDeserialize + codec::FullCodec;
 		/// The class properties type
 		type ClassData: Parameter + Member + MaybeSerializeDeserialize;
 		/// The token properties type
 		type TokenData: Parameter + Member + MaybeSerializeDeserialize;
+		/// The account holding the children of a token
+		type TokenAccount: Convert<(Self::ClassId, Self::TokenId), Self::AccountId>;
+		/// The maximum depth of nested tokens
+		#[pallet::constant]
+		type MaxNestingDepth: Get<u32>;
 	}
 
 	pub type ClassInfoOf<T> =
-		ClassInfo<<T as Config>::TokenId, <T as frame_system::Config>::AccountId, <T as Config>::ClassData>;
-	pub type TokenInfoOf<T> = TokenInfo<<T as frame_system::Config>::AccountId, <T as Config>::TokenData>;
+		ClassInfo<<T as Config>::Quantity, <T as frame_system::Config>::AccountId, <T as Config>::ClassData>;
+	pub type TokenInfoOf<T> = TokenInfo<<T as Config>::Quantity, <T as Config>::TokenData>;
 
 	pub type GenesisTokenData<T> = (
 		<T as frame_system::Config>::AccountId, // Token owner
 		Vec<u8>,                                // Token metadata
 		<T as Config>::TokenData,
+		<T as Config>::Quantity, // Token quantity
 	);
 	pub type GenesisTokens<T> = (
 		<T as frame_system::Config>::AccountId, // Token class owner
@@ -108,6 +128,16 @@ pub mod module {
 		/// Can not destroy class
 		/// Total issuance is not 0
 		CannotDestroyClass,
+		/// Quantity must be greater than zero
+		InvalidQuantity,
+		/// The token can not be nested into the parent
+		CannotNest,
+		/// Nesting would exceed `MaxNestingDepth`
+		TooDeep,
+		/// The token is not nested
+		NotNested,
+		/// The token still owns other tokens
+		HasChildren,
 	}
 
 	/// Next available class ID.
@@ -135,14 +165,43 @@ pub mod module {
 	pub type Tokens<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::TokenId, TokenInfoOf<T>>;
 
-	/// Token existence check by owner and class ID.
-	// TODO: pallet macro doesn't support conditional compiling. Always having `TokensByOwner` storage doesn't hurt but
-	// it could be removed once conditional compiling supported.
-	// #[cfg(not(feature = "disable-tokens-by-owner"))]
+	/// Token balance by owner and class ID.
 	#[pallet::storage]
 	#[pallet::getter(fn tokens_by_owner)]
 	pub type TokensByOwner<T: Config> =
-		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, (T::ClassId, T::TokenId), (), ValueQuery>;
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, (T::ClassId, T::TokenId), T::Quantity, ValueQuery>;
+
+	/// The account approved to transfer a token on behalf of a holder.
+	///
+	/// Cleared once the holder transfers or burns any quantity of the token.
+	#[pallet::storage]
+	#[pallet::getter(fn approvals)]
+	pub type Approvals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, (T::ClassId, T::TokenId), Twox64Concat, T::AccountId, T::AccountId>;
+
+	/// Operators approved to transfer all tokens of an owner.
+	#[pallet::storage]
+	#[pallet::getter(fn operator_approvals)]
+	pub type OperatorApprovals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::AccountId, (), ValueQuery>;
+
+	/// Accounts holding a non-zero balance of a token.
+	#[pallet::storage]
+	#[pallet::getter(fn owners_by_token)]
+	pub type OwnersByToken<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, (T::ClassId, T::TokenId), Twox64Concat, T::AccountId, (), ValueQuery>;
+
+	/// The parent of a nested token.
+	#[pallet::storage]
+	#[pallet::getter(fn parents)]
+	pub type Parents<T: Config> =
+		StorageMap<_, Twox64Concat, (T::ClassId, T::TokenId), (T::ClassId, T::TokenId)>;
+
+	/// Tokens nested into a token.
+	#[pallet::storage]
+	#[pallet::getter(fn children)]
+	pub type Children<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, (T::ClassId, T::TokenId), Twox64Concat, (T::ClassId, T::TokenId), (), ValueQuery>;
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
@@ -162,8 +221,8 @@ pub mod module {
 			self.tokens.iter().for_each(|token_class| {
 				let class_id = Pallet::<T>::create_class(&token_class.0, token_class.1.to_vec(), token_class.2.clone())
 					.expect("Create class cannot fail while building genesis");
-				for (account_id, token_metadata, token_data) in &token_class.3 {
-					Pallet::<T>::mint(&account_id, class_id, token_metadata.to_vec(), token_data.clone())
+				for (account_id, token_metadata, token_data, quantity) in &token_class.3 {
+					Pallet::<T>::mint(&account_id, class_id, token_metadata.to_vec(), token_data.clone(), *quantity)
 						.expect("Token mint cannot fail during genesis");
 				}
 			})
@@ -204,35 +263,42 @@ impl<T: Config> Pallet<T> {
 		Ok(class_id)
 	}
 
-	/// Transfer NFT(non fungible token) from `from` account to `to` account
-	pub fn transfer(from: &T::AccountId, to: &T::AccountId, token: (T::ClassId, T::TokenId)) -> DispatchResult {
-		Tokens::<T>::try_mutate(token.0, token.1, |token_info| -> DispatchResult {
-			let mut info = token_info.as_mut().ok_or(Error::<T>::TokenNotFound)?;
-			ensure!(info.owner == *from, Error::<T>::NoPermission);
-			if from == to {
-				// no change needed
-				return Ok(());
-			}
+	/// Transfer `quantity` of NFT(non fungible token) from `from` account to `to` account
+	pub fn transfer(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		quantity: T::Quantity,
+	) -> DispatchResult {
+		ensure!(!quantity.is_zero(), Error::<T>::InvalidQuantity);
+		ensure!(Tokens::<T>::contains_key(token.0, token.1), Error::<T>::TokenNotFound);
+
+		let from_balance = Self::tokens_by_owner(from, token);
+		ensure!(from_balance >= quantity, Error::<T>::NoPermission);
+		if from == to {
+			// no change needed
+			return Ok(());
+		}
 
-			info.owner = to.clone();
+		let to_balance = Self::tokens_by_owner(to, token)
+			.checked_add(&quantity)
+			.ok_or(Error::<T>::NumOverflow)?;
+		Approvals::<T>::remove(token, from);
+		Self::set_balance(from, token, from_balance - quantity);
+		Self::set_balance(to, token, to_balance);
 
-			#[cfg(not(feature = "disable-tokens-by-owner"))]
-			{
-				TokensByOwner::<T>::remove(from, token);
-				TokensByOwner::<T>::insert(to, token, ());
-			}
-
-			Ok(())
-		})
+		Ok(())
 	}
 
-	/// Mint NFT(non fungible token) to `owner`
+	/// Mint `quantity` of a new NFT(non fungible token) to `owner`
 	pub fn mint(
 		owner: &T::AccountId,
 		class_id: T::ClassId,
 		metadata: Vec<u8>,
 		data: T::TokenData,
+		quantity: T::Quantity,
 	) -> Result<T::TokenId, DispatchError> {
+		ensure!(!quantity.is_zero(), Error::<T>::InvalidQuantity);
 		NextTokenId::<T>::try_mutate(class_id, |id| -> Result<T::TokenId, DispatchError> {
 			let token_id = *id;
 			*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableTokenId)?;
@@ -241,46 +307,119 @@ impl<T: Config> Pallet<T> {
 				let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
 				info.total_issuance = info
 					.total_issuance
-					.checked_add(&One::one())
+					.checked_add(&quantity)
 					.ok_or(Error::<T>::NumOverflow)?;
 				Ok(())
 			})?;
 
 			let token_info = TokenInfo {
 				metadata,
-				owner: owner.clone(),
+				quantity,
 				data,
 			};
 			Tokens::<T>::insert(class_id, token_id, token_info);
-			#[cfg(not(feature = "disable-tokens-by-owner"))]
-			TokensByOwner::<T>::insert(owner, (class_id, token_id), ());
+			Self::set_balance(owner, (class_id, token_id), quantity);
 
 			Ok(token_id)
 		})
 	}
 
-	/// Burn NFT(non fungible token) from `owner`
-	pub fn burn(owner: &T::AccountId, token: (T::ClassId, T::TokenId)) -> DispatchResult {
+	/// Burn `quantity` of NFT(non fungible token) from `owner`
+	///
+	/// The token is removed once its whole quantity is burned.
+	pub fn burn(owner: &T::AccountId, token: (T::ClassId, T::TokenId), quantity: T::Quantity) -> DispatchResult {
+		ensure!(!quantity.is_zero(), Error::<T>::InvalidQuantity);
+		ensure!(!Self::has_children(token), Error::<T>::HasChildren);
 		Tokens::<T>::try_mutate_exists(token.0, token.1, |token_info| -> DispatchResult {
-			let t = token_info.take().ok_or(Error::<T>::TokenNotFound)?;
-			ensure!(t.owner == *owner, Error::<T>::NoPermission);
+			let mut t = token_info.take().ok_or(Error::<T>::TokenNotFound)?;
+			let balance = Self::tokens_by_owner(owner, token);
+			ensure!(balance >= quantity, Error::<T>::NoPermission);
 
 			Classes::<T>::try_mutate(token.0, |class_info| -> DispatchResult {
 				let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
 				info.total_issuance = info
 					.total_issuance
-					.checked_sub(&One::one())
+					.checked_sub(&quantity)
 					.ok_or(Error::<T>::NumOverflow)?;
 				Ok(())
 			})?;
 
-			#[cfg(not(feature = "disable-tokens-by-owner"))]
-			TokensByOwner::<T>::remove(owner, token);
+			t.quantity = t.quantity.checked_sub(&quantity).ok_or(Error::<T>::NumOverflow)?;
+			Approvals::<T>::remove(token, owner);
+			Self::set_balance(owner, token, balance - quantity);
+			if !t.quantity.is_zero() {
+				*token_info = Some(t);
+			} else if let Some(parent) = Parents::<T>::take(token) {
+				Children::<T>::remove(parent, token);
+			}
 
 			Ok(())
 		})
 	}
 
+	/// Approve `spender` to transfer `token` held by `owner`, `None` removes the approval
+	pub fn approve(
+		owner: &T::AccountId,
+		spender: Option<T::AccountId>,
+		token: (T::ClassId, T::TokenId),
+	) -> DispatchResult {
+		ensure!(Tokens::<T>::contains_key(token.0, token.1), Error::<T>::TokenNotFound);
+		ensure!(Self::is_owner(owner, token), Error::<T>::NoPermission);
+		match spender {
+			Some(spender) => Approvals::<T>::insert(token, owner, spender),
+			None => Approvals::<T>::remove(token, owner),
+		}
+		Ok(())
+	}
+
+	/// Approve or disapprove `operator` to transfer all tokens of `owner`
+	pub fn set_approval_for_all(owner: &T::AccountId, operator: &T::AccountId, approved: bool) {
+		if approved {
+			OperatorApprovals::<T>::insert(owner, operator, ());
+		} else {
+			OperatorApprovals::<T>::remove(owner, operator);
+		}
+	}
+
+	/// Nest `child` held by `owner` into `parent`, whose root token is owned by `owner`.
+	///
+	/// The whole quantity of `child` moves to the account of `parent`. A token
+	/// already owning other tokens can not be nested, so trees are built from
+	/// the root.
+	pub fn nest(
+		owner: &T::AccountId,
+		child: (T::ClassId, T::TokenId),
+		parent: (T::ClassId, T::TokenId),
+	) -> DispatchResult {
+		ensure!(child != parent, Error::<T>::CannotNest);
+		let parent_info = Tokens::<T>::get(parent.0, parent.1).ok_or(Error::<T>::TokenNotFound)?;
+		ensure!(parent_info.quantity == One::one(), Error::<T>::CannotNest);
+		ensure!(Self::root_owner(parent).as_ref() == Some(owner), Error::<T>::NoPermission);
+		ensure!(Self::owner_of(child).as_ref() == Some(owner), Error::<T>::NoPermission);
+		ensure!(!Parents::<T>::contains_key(child), Error::<T>::CannotNest);
+		ensure!(!Self::has_children(child), Error::<T>::HasChildren);
+		ensure!(Self::depth(parent) < T::MaxNestingDepth::get(), Error::<T>::TooDeep);
+
+		let quantity = Self::tokens_by_owner(owner, child);
+		Self::transfer(owner, &Self::token_account(parent), child, quantity)?;
+		Parents::<T>::insert(child, parent);
+		Children::<T>::insert(parent, child, ());
+		Ok(())
+	}
+
+	/// Move nested `child` out of its parent to `owner`, who owns the root token.
+	pub fn unnest(owner: &T::AccountId, child: (T::ClassId, T::TokenId)) -> DispatchResult {
+		let parent = Parents::<T>::get(child).ok_or(Error::<T>::NotNested)?;
+		ensure!(Self::root_owner(parent).as_ref() == Some(owner), Error::<T>::NoPermission);
+
+		let holder = Self::token_account(parent);
+		let quantity = Self::tokens_by_owner(&holder, child);
+		Self::transfer(&holder, owner, child, quantity)?;
+		Parents::<T>::remove(child);
+		Children::<T>::remove(parent, child);
+		Ok(())
+	}
+
 	/// Destroy NFT(non fungible token) class
 	pub fn destroy_class(owner: &T::AccountId, class_id: T::ClassId) -> DispatchResult {
 		Classes::<T>::try_mutate_exists(class_id, |class_info| -> DispatchResult {
@@ -294,11 +433,66 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Whether `account` holds any quantity of `token`
 	pub fn is_owner(account: &T::AccountId, token: (T::ClassId, T::TokenId)) -> bool {
-		#[cfg(feature = "disable-tokens-by-owner")]
-		return Tokens::<T>::get(token.0, token.1).map_or(false, |token| token.owner == *account);
-
-		#[cfg(not(feature = "disable-tokens-by-owner"))]
 		TokensByOwner::<T>::contains_key(account, token)
 	}
+
+	/// Whether `operator` is allowed to transfer `token` held by `owner`
+	pub fn is_approved(operator: &T::AccountId, owner: &T::AccountId, token: (T::ClassId, T::TokenId)) -> bool {
+		operator == owner
+			|| OperatorApprovals::<T>::contains_key(owner, operator)
+			|| Approvals::<T>::get(token, owner).as_ref() == Some(operator)
+	}
+
+	/// The only holder of `token`, `None` if the token does not exist or its
+	/// quantity is split between several accounts.
+	pub fn owner_of(token: (T::ClassId, T::TokenId)) -> Option<T::AccountId> {
+		let mut holders = OwnersByToken::<T>::iter_prefix(token).map(|(who, _)| who);
+		match (holders.next(), holders.next()) {
+			(Some(who), None) => Some(who),
+			_ => None,
+		}
+	}
+
+	/// The account holding the children of `token`
+	pub fn token_account(token: (T::ClassId, T::TokenId)) -> T::AccountId {
+		T::TokenAccount::convert(token)
+	}
+
+	/// The owner of the root token of `token`, `None` if the root token has no
+	/// single holder.
+	pub fn root_owner(token: (T::ClassId, T::TokenId)) -> Option<T::AccountId> {
+		let mut root = token;
+		while let Some(parent) = Parents::<T>::get(root) {
+			root = parent;
+		}
+		Self::owner_of(root)
+	}
+
+	/// Whether other tokens are nested into `token`
+	pub fn has_children(token: (T::ClassId, T::TokenId)) -> bool {
+		Children::<T>::iter_prefix(token).next().is_some()
+	}
+
+	/// The number of ancestors of `token`, bounded by `MaxNestingDepth`
+	fn depth(token: (T::ClassId, T::TokenId)) -> u32 {
+		let mut depth = 0u32;
+		let mut current = token;
+		while let Some(parent) = Parents::<T>::get(current) {
+			depth = depth.saturating_add(1);
+			current = parent;
+		}
+		depth
+	}
+
+	fn set_balance(account: &T::AccountId, token: (T::ClassId, T::TokenId), balance: T::Quantity) {
+		if balance.is_zero() {
+			TokensByOwner::<T>::remove(account, token);
+			OwnersByToken::<T>::remove(token, account);
+		} else {
+			TokensByOwner::<T>::insert(account, token, balance);
+			OwnersByToken::<T>::insert(token, account, ());
+		}
+	}
 }
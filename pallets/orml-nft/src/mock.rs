@@ -42,11 +42,25 @@ impl frame_system::Config for Runtime {
 	type SS58Prefix = ();
 }
 
+parameter_types! {
+	pub const MaxNestingDepth: u32 = 2;
+}
+
+pub struct TokenAccount;
+impl Convert<(u64, u64), AccountId> for TokenAccount {
+	fn convert((class_id, token_id): (u64, u64)) -> AccountId {
+		AccountId::max_value() - ((class_id as AccountId) << 64 | token_id as AccountId)
+	}
+}
+
 impl Config for Runtime {
 	type ClassId = u64;
 	type TokenId = u64;
+	type Quantity = u32;
 	type ClassData = ();
 	type TokenData = ();
+	type TokenAccount = TokenAccount;
+	type MaxNestingDepth = MaxNestingDepth;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -31,15 +31,15 @@ fn mint_should_work() {
 		assert_eq!(next_class_id, CLASS_ID);
 		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
 		assert_eq!(NonFungibleTokenModule::next_token_id(CLASS_ID), 0);
-		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 1));
 		assert_eq!(NonFungibleTokenModule::next_token_id(CLASS_ID), 1);
-		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 1));
 		assert_eq!(NonFungibleTokenModule::next_token_id(CLASS_ID), 2);
 
 		let next_class_id = NonFungibleTokenModule::next_class_id();
 		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
 		assert_eq!(NonFungibleTokenModule::next_token_id(next_class_id), 0);
-		assert_ok!(NonFungibleTokenModule::mint(&BOB, next_class_id, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, next_class_id, vec![1], (), 1));
 		assert_eq!(NonFungibleTokenModule::next_token_id(next_class_id), 1);
 
 		assert_eq!(NonFungibleTokenModule::next_token_id(CLASS_ID), 2);
@@ -51,16 +51,16 @@ fn mint_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
 		Classes::<Runtime>::mutate(CLASS_ID, |class_info| {
-			class_info.as_mut().unwrap().total_issuance = <Runtime as Config>::TokenId::max_value();
+			class_info.as_mut().unwrap().total_issuance = <Runtime as Config>::Quantity::max_value();
 		});
 		assert_noop!(
-			NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()),
+			NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 1),
 			Error::<Runtime>::NumOverflow
 		);
 
 		NextTokenId::<Runtime>::mutate(CLASS_ID, |id| *id = <Runtime as Config>::TokenId::max_value());
 		assert_noop!(
-			NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()),
+			NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 1),
 			Error::<Runtime>::NoAvailableTokenId
 		);
 	});
@@ -70,10 +70,10 @@ fn mint_should_fail() {
 fn transfer_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
-		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
-		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &BOB, (CLASS_ID, TOKEN_ID)));
-		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID)));
-		assert_ok!(NonFungibleTokenModule::transfer(&ALICE, &BOB, (CLASS_ID, TOKEN_ID)));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 1));
+		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &BOB, (CLASS_ID, TOKEN_ID), 1));
+		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID), 1));
+		assert_ok!(NonFungibleTokenModule::transfer(&ALICE, &BOB, (CLASS_ID, TOKEN_ID), 1));
 		assert!(NonFungibleTokenModule::is_owner(&BOB, (CLASS_ID, TOKEN_ID)));
 	});
 }
@@ -82,32 +82,106 @@ fn transfer_should_work() {
 fn transfer_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
-		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 1));
 		assert_noop!(
-			NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID_NOT_EXIST)),
+			NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID_NOT_EXIST), 1),
 			Error::<Runtime>::TokenNotFound
 		);
 		assert_noop!(
-			NonFungibleTokenModule::transfer(&ALICE, &BOB, (CLASS_ID, TOKEN_ID)),
+			NonFungibleTokenModule::transfer(&ALICE, &BOB, (CLASS_ID, TOKEN_ID), 1),
 			Error::<Runtime>::NoPermission
 		);
 		assert_noop!(
-			NonFungibleTokenModule::mint(&BOB, CLASS_ID_NOT_EXIST, vec![1], ()),
+			NonFungibleTokenModule::mint(&BOB, CLASS_ID_NOT_EXIST, vec![1], (), 1),
 			Error::<Runtime>::ClassNotFound
 		);
 		assert_noop!(
-			NonFungibleTokenModule::transfer(&ALICE, &ALICE, (CLASS_ID, TOKEN_ID)),
+			NonFungibleTokenModule::transfer(&ALICE, &ALICE, (CLASS_ID, TOKEN_ID), 1),
 			Error::<Runtime>::NoPermission
 		);
 	});
 }
 
+#[test]
+fn partial_transfer_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 10));
+		assert_eq!(NonFungibleTokenModule::owner_of((CLASS_ID, TOKEN_ID)), Some(BOB));
+
+		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID), 4));
+		assert_eq!(NonFungibleTokenModule::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID)), 6);
+		assert_eq!(NonFungibleTokenModule::tokens_by_owner(ALICE, (CLASS_ID, TOKEN_ID)), 4);
+		assert_eq!(NonFungibleTokenModule::owner_of((CLASS_ID, TOKEN_ID)), None);
+		assert_noop!(
+			NonFungibleTokenModule::transfer(&ALICE, &BOB, (CLASS_ID, TOKEN_ID), 5),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NonFungibleTokenModule::transfer(&ALICE, &BOB, (CLASS_ID, TOKEN_ID), 0),
+			Error::<Runtime>::InvalidQuantity
+		);
+
+		assert_ok!(NonFungibleTokenModule::transfer(&ALICE, &BOB, (CLASS_ID, TOKEN_ID), 4));
+		assert!(!NonFungibleTokenModule::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NonFungibleTokenModule::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID)), 10);
+		assert_eq!(NonFungibleTokenModule::owner_of((CLASS_ID, TOKEN_ID)), Some(BOB));
+	});
+}
+
+#[test]
+fn partial_burn_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 10));
+		assert_eq!(NonFungibleTokenModule::classes(CLASS_ID).unwrap().total_issuance, 10);
+
+		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID), 3));
+		assert_eq!(NonFungibleTokenModule::tokens(CLASS_ID, TOKEN_ID).unwrap().quantity, 7);
+		assert_eq!(NonFungibleTokenModule::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID)), 7);
+		assert_eq!(NonFungibleTokenModule::classes(CLASS_ID).unwrap().total_issuance, 7);
+
+		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID), 7));
+		assert!(!Tokens::<Runtime>::contains_key(CLASS_ID, TOKEN_ID));
+		assert!(!NonFungibleTokenModule::is_owner(&BOB, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NonFungibleTokenModule::classes(CLASS_ID).unwrap().total_issuance, 0);
+	});
+}
+
+#[test]
+fn approve_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 2));
+		assert_noop!(
+			NonFungibleTokenModule::approve(&ALICE, Some(ALICE), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NonFungibleTokenModule::approve(&BOB, Some(ALICE), (CLASS_ID, TOKEN_ID_NOT_EXIST)),
+			Error::<Runtime>::TokenNotFound
+		);
+
+		assert_ok!(NonFungibleTokenModule::approve(&BOB, Some(ALICE), (CLASS_ID, TOKEN_ID)));
+		assert!(NonFungibleTokenModule::is_approved(&ALICE, &BOB, (CLASS_ID, TOKEN_ID)));
+		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID), 1));
+		assert!(!NonFungibleTokenModule::is_approved(&ALICE, &BOB, (CLASS_ID, TOKEN_ID)));
+
+		NonFungibleTokenModule::set_approval_for_all(&BOB, &ALICE, true);
+		assert!(NonFungibleTokenModule::is_approved(&ALICE, &BOB, (CLASS_ID, TOKEN_ID)));
+		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID), 1));
+		assert!(NonFungibleTokenModule::is_approved(&ALICE, &BOB, (CLASS_ID, TOKEN_ID)));
+		NonFungibleTokenModule::set_approval_for_all(&BOB, &ALICE, false);
+		assert!(!NonFungibleTokenModule::is_approved(&ALICE, &BOB, (CLASS_ID, TOKEN_ID)));
+	});
+}
+
 #[test]
 fn burn_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
-		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
-		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID)));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 1));
+		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID), 1));
 	});
 }
 
@@ -115,27 +189,27 @@ fn burn_should_work() {
 fn burn_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
-		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 1));
 		assert_noop!(
-			NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID_NOT_EXIST)),
+			NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID_NOT_EXIST), 1),
 			Error::<Runtime>::TokenNotFound
 		);
 
 		assert_noop!(
-			NonFungibleTokenModule::burn(&ALICE, (CLASS_ID, TOKEN_ID)),
+			NonFungibleTokenModule::burn(&ALICE, (CLASS_ID, TOKEN_ID), 1),
 			Error::<Runtime>::NoPermission
 		);
 	});
 
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
-		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 1));
 
 		Classes::<Runtime>::mutate(CLASS_ID, |class_info| {
 			class_info.as_mut().unwrap().total_issuance = 0;
 		});
 		assert_noop!(
-			NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID)),
+			NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID), 1),
 			Error::<Runtime>::NumOverflow
 		);
 	});
@@ -145,8 +219,8 @@ fn burn_should_fail() {
 fn destroy_class_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
-		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
-		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID)));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 1));
+		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID), 1));
 		assert_ok!(NonFungibleTokenModule::destroy_class(&ALICE, CLASS_ID));
 		assert_eq!(Classes::<Runtime>::contains_key(CLASS_ID), false);
 		assert_eq!(NextTokenId::<Runtime>::contains_key(CLASS_ID), false);
@@ -157,7 +231,7 @@ fn destroy_class_should_work() {
 fn destroy_class_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
-		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 1));
 		assert_noop!(
 			NonFungibleTokenModule::destroy_class(&ALICE, CLASS_ID_NOT_EXIST),
 			Error::<Runtime>::ClassNotFound
@@ -173,8 +247,52 @@ fn destroy_class_should_fail() {
 			Error::<Runtime>::CannotDestroyClass
 		);
 
-		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID)));
+		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID), 1));
 		assert_ok!(NonFungibleTokenModule::destroy_class(&ALICE, CLASS_ID));
 		assert_eq!(Classes::<Runtime>::contains_key(CLASS_ID), false);
 	});
 }
+
+#[test]
+fn nest_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		for _ in 0..4 {
+			assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], (), 1));
+		}
+		let (root, child, grandchild, other) = ((CLASS_ID, 0), (CLASS_ID, 1), (CLASS_ID, 2), (CLASS_ID, 3));
+
+		assert_noop!(
+			NonFungibleTokenModule::nest(&ALICE, child, root),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NonFungibleTokenModule::nest(&BOB, child, root));
+		assert_ok!(NonFungibleTokenModule::nest(&BOB, grandchild, child));
+		assert_eq!(NonFungibleTokenModule::owner_of(child), Some(NonFungibleTokenModule::token_account(root)));
+		assert_eq!(NonFungibleTokenModule::root_owner(grandchild), Some(BOB));
+		assert_noop!(
+			NonFungibleTokenModule::nest(&BOB, other, grandchild),
+			Error::<Runtime>::TooDeep
+		);
+		assert_noop!(
+			NonFungibleTokenModule::nest(&BOB, root, other),
+			Error::<Runtime>::HasChildren
+		);
+
+		// The tree moves with its root.
+		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &ALICE, root, 1));
+		assert_eq!(NonFungibleTokenModule::root_owner(grandchild), Some(ALICE));
+		assert_noop!(
+			NonFungibleTokenModule::burn(&ALICE, root, 1),
+			Error::<Runtime>::HasChildren
+		);
+		assert_noop!(
+			NonFungibleTokenModule::unnest(&BOB, grandchild),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NonFungibleTokenModule::unnest(&ALICE, grandchild));
+		assert_eq!(NonFungibleTokenModule::tokens_by_owner(ALICE, grandchild), 1);
+		assert_eq!(NonFungibleTokenModule::parents(grandchild), None);
+		assert!(!NonFungibleTokenModule::has_children(child));
+	});
+}
@@ -53,13 +53,13 @@ use frame_system::{ensure_signed, pallet_prelude::*};
 use orml_traits::{
 	account::MergeAccount,
 	arithmetic::{self, Signed},
-	BalanceStatus, GetByKey, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
-	MultiReservableCurrency, OnDust,
+	BalanceStatus, CreateCurrency, GetByKey, LockIdentifier, MultiCurrency, MultiCurrencyExtended,
+	MultiLockableCurrency, MultiReservableCurrency, OnDust,
 };
 use sp_runtime::{
 	traits::{
 		AccountIdConversion, AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member,
-		Saturating, StaticLookup, Zero,
+		One, Saturating, StaticLookup, Zero,
 	},
 	DispatchError, DispatchResult, ModuleId, RuntimeDebug,
 };
@@ -184,6 +184,11 @@ pub mod module {
 
 		/// Handler to burn or transfer account's dust
 		type OnDust: OnDust<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
+		/// The first currency ID assigned by `create_currency`, the IDs below it are reserved for
+		/// currencies registered elsewhere.
+		#[pallet::constant]
+		type FirstCreatedCurrencyId: Get<Self::CurrencyId>;
 	}
 
 	#[pallet::error]
@@ -200,6 +205,8 @@ pub mod module {
 		LiquidityRestrictions,
 		/// Account still has active reserved
 		StillHasActiveReserved,
+		/// No available currency ID
+		NoAvailableCurrencyId,
 	}
 
 	#[pallet::event]
@@ -211,6 +218,8 @@ pub mod module {
 		/// ExistentialDeposit, resulting in an outright loss. \[account,
 		/// currency_id, amount\]
 		DustLost(T::AccountId, T::CurrencyId, T::Balance),
+		/// A new currency was created. \[currency_id, owner\]
+		CurrencyCreated(T::CurrencyId, T::AccountId),
 	}
 
 	/// The total issuance of a token type.
@@ -218,6 +227,16 @@ pub mod module {
 	#[pallet::getter(fn total_issuance)]
 	pub type TotalIssuance<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, T::Balance, ValueQuery>;
 
+	/// The next currency ID tried by `create_currency`.
+	#[pallet::storage]
+	#[pallet::getter(fn next_currency_id)]
+	pub type NextCurrencyId<T: Config> = StorageValue<_, T::CurrencyId, ValueQuery>;
+
+	/// The owners of currencies created by `create_currency`.
+	#[pallet::storage]
+	#[pallet::getter(fn currency_owners)]
+	pub type CurrencyOwners<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, T::AccountId>;
+
 	/// Any liquidity locks of a token type under an account.
 	/// NOTE: Should only be accessed when setting, changing and freeing a lock.
 	#[pallet::storage]
@@ -466,6 +485,29 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> CreateCurrency<T::AccountId> for Pallet<T> {
+	type CurrencyId = T::CurrencyId;
+
+	fn create_currency(owner: &T::AccountId) -> sp_std::result::Result<Self::CurrencyId, DispatchError> {
+		NextCurrencyId::<T>::try_mutate(|next_id| -> sp_std::result::Result<T::CurrencyId, DispatchError> {
+			// Skip currencies already issued without being created, e.g. endowed in genesis.
+			let mut currency_id = (*next_id).max(T::FirstCreatedCurrencyId::get());
+			while !Self::total_issuance(currency_id).is_zero() || CurrencyOwners::<T>::contains_key(currency_id) {
+				currency_id = currency_id
+					.checked_add(&One::one())
+					.ok_or(Error::<T>::NoAvailableCurrencyId)?;
+			}
+			*next_id = currency_id
+				.checked_add(&One::one())
+				.ok_or(Error::<T>::NoAvailableCurrencyId)?;
+
+			CurrencyOwners::<T>::insert(currency_id, owner);
+			Self::deposit_event(Event::CurrencyCreated(currency_id, owner.clone()));
+			Ok(currency_id)
+		})
+	}
+}
+
 impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 	type CurrencyId = T::CurrencyId;
 	type Balance = T::Balance;
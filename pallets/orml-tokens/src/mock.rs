@@ -204,6 +204,7 @@ parameter_type_with_key! {
 
 parameter_types! {
 	pub DustAccount: AccountId = ModuleId(*b"orml/dst").into_account();
+	pub const FirstCreatedCurrencyId: CurrencyId = 0;
 }
 
 impl Config for Runtime {
@@ -214,6 +215,7 @@ impl Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = TransferDust<Runtime, DustAccount>;
+	type FirstCreatedCurrencyId = FirstCreatedCurrencyId;
 }
 pub type TreasuryCurrencyAdapter = <Runtime as pallet_treasury::Config>::Currency;
 
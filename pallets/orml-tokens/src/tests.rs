@@ -971,3 +971,19 @@ fn currency_adapter_transferring_too_high_value_should_not_panic() {
 		assert_eq!(TreasuryCurrencyAdapter::free_balance(&ALICE), 2);
 	});
 }
+
+#[test]
+fn create_currency_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::create_currency(&ALICE), 0);
+			let created_event = Event::tokens(crate::Event::CurrencyCreated(0, ALICE));
+			assert!(System::events().iter().any(|record| record.event == created_event));
+			// DOT is issued in genesis, so it is skipped.
+			assert_ok!(Tokens::create_currency(&BOB), BTC);
+			assert_eq!(Tokens::currency_owners(BTC), Some(BOB));
+			assert_eq!(Tokens::next_currency_id(), ETH);
+		});
+}
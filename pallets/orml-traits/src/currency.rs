@@ -333,6 +333,16 @@ pub trait BasicReservableCurrency<AccountId>: BasicCurrency<AccountId> {
 	) -> result::Result<Self::Balance, DispatchError>;
 }
 
+/// Create new currencies.
+pub trait CreateCurrency<AccountId> {
+	/// The currency identifier.
+	type CurrencyId;
+
+	/// Create a new currency owned by `owner`, returning its identifier.
+	/// The new currency has no issuance.
+	fn create_currency(owner: &AccountId) -> result::Result<Self::CurrencyId, DispatchError>;
+}
+
 /// Handler for account which has dust, need to burn or recycle it
 pub trait OnDust<AccountId, CurrencyId, Balance> {
 	fn on_dust(who: &AccountId, currency_id: CurrencyId, amount: Balance);
@@ -41,6 +41,11 @@ pub type ClassId = u32;
 /// NFT token ID type.
 pub type TokenId = u64;
 
+/// NFT token quantity type.
+pub type Quantity = u32;
+
 /// NFT category ID type.
 pub type CategoryId = u32;
 
+/// Global ID type for auctions, offers and other entities.
+pub type GlobalId = u64;